@@ -1,4 +0,0 @@
-rustc -O \
-      -L /code/meow \
-      -L .. \
-      comparison.rs
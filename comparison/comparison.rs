@@ -1,170 +1,238 @@
-use std;
-use meow;
-use search;
+use std::time::Instant;
 
-fn BM(haystack: str, needle: str) -> [uint] {
+fn bm(haystack: &str, needle: &str) -> Vec<usize> {
    search::boyer_moore_search(haystack, needle,
-                           str::len(haystack),
-                           0u, str::len(haystack))
+                              haystack.len(),
+                              0, haystack.len())
 }
 
-fn BMH(haystack: str, needle: str) -> [uint] {
+fn bmh(haystack: &str, needle: &str) -> Vec<usize> {
    search::boyer_moore_horspool_search(haystack, needle,
-                           str::len(haystack),
-                           0u, str::len(haystack))
+                                       haystack.len(),
+                                       0, haystack.len())
 }
 
-fn simple(haystack: str, needle: str) -> [uint] {
+fn simple(haystack: &str, needle: &str) -> Vec<usize> {
    search::simple_search(haystack, needle,
-                      str::len(haystack),
-                      0u, str::len(haystack))
+                         haystack.len(),
+                         0, haystack.len())
 }
 
-fn compareHN(hlen: uint, nlen: uint) -> (float, float) {
+fn two_way(haystack: &str, needle: &str) -> Vec<usize> {
+   search::two_way_search(haystack, needle,
+                          haystack.len(),
+                          0, haystack.len())
+}
+
+// a small xorshift generator, so the comparison has no dependencies
+struct Rng(u64);
+
+impl Rng {
+   fn gen_str(&mut self, len: usize) -> String {
+      self.gen_str_over(len, 95)
+   }
+
+   // a string of the first `alphabet` printable characters
+   fn gen_str_over(&mut self, len: usize, alphabet: u64) -> String {
+      (0..len).map(|_| {
+         self.0 ^= self.0 << 13;
+         self.0 ^= self.0 >> 7;
+         self.0 ^= self.0 << 17;
+         (b' ' + (self.0 % alphabet) as u8) as char
+      }).collect()
+   }
+}
+
+fn measure_time_and_value<T>(f: impl FnOnce() -> T) -> (T, u128) {
+   let started = Instant::now();
+   let value = f();
+   (value, started.elapsed().as_nanos().max(1))
+}
+
+// the best of several runs, in nanoseconds per haystack byte
+fn best_time(haystack: &str, needle: &str,
+             search: fn(&str, &str) -> Vec<usize>) -> f64 {
+   let reps = (1 << 16) / haystack.len().max(1) + 8;
+   let best = (0..reps)
+      .map(|_| measure_time_and_value(|| search(haystack, needle)).1)
+      .min()
+      .unwrap();
+   best as f64 / haystack.len().max(1) as f64
+}
+
+// the time of `search` over the time of `other`, averaged over a few
+// random needles and haystacks of the first `alphabet` characters
+fn time_ratio(generator: &mut Rng, hlen: usize, nlen: usize, alphabet: u64,
+              search: fn(&str, &str) -> Vec<usize>,
+              other: fn(&str, &str) -> Vec<usize>) -> f64 {
+   let samples = 5;
+   (0..samples).map(|_| {
+      let needle   = generator.gen_str_over(nlen, alphabet);
+      let haystack = generator.gen_str_over(hlen, alphabet);
+      assert_eq!(search(&haystack, &needle), other(&haystack, &needle));
+      best_time(&haystack, &needle, search) / best_time(&haystack, &needle, other)
+   }).sum::<f64>() / samples as f64
+}
+
+// where each algorithm starts to beat the one before it,
+// as the thresholds `search::Thresholds` chooses between them with
+fn crossovers(generator: &mut Rng) {
+   println!("% crossovers (time / naive time, or / Horspool time for Boyer-Moore and Two-Way)");
+
+   // needle lengths, on a long haystack of text
+   print!("% Horspool, by needle length on 64KiB:");
+   for nlen in 2..=8 {
+      print!(" {}:{:.2}", nlen, time_ratio(generator, 1 << 16, nlen, 95, bmh, simple));
+   }
+   println!();
+
+   // haystack lengths, for a short needle and for a longer one
+   for nlen in [4, 16, 64] {
+      print!("% Horspool, by haystack length for {}-byte needles:", nlen);
+      for hlen in [64, 96, 128, 160, 192, 256, 384, 512] {
+         print!(" {}:{:.2}", hlen, time_ratio(generator, hlen, nlen, 95, bmh, simple));
+      }
+      println!();
+   }
+
+   // small alphabets, where Boyer-Moore's good-suffix shifts help
+   for alphabet in [2, 4, 8, 16] {
+      print!("% Boyer-Moore, by needle length over {} bytes:", alphabet);
+      for nlen in [4, 16, 64, 256] {
+         print!(" {}:{:.2}", nlen, time_ratio(generator, 1 << 16, nlen, alphabet, bm, bmh));
+      }
+      println!();
+   }
+
+   // Two-Way, which trades speed for constant space and a linear worst case
+   for alphabet in [4, 95] {
+      print!("% Two-Way, by needle length over {} bytes:", alphabet);
+      for nlen in [4, 8, 16, 32] {
+         print!(" {}:{:.2}", nlen, time_ratio(generator, 1 << 16, nlen, alphabet, two_way, bmh));
+      }
+      println!();
+   }
+}
+
+fn compare_hn(generator: &mut Rng, hlen: usize, nlen: usize) -> (f64, f64) {
    // some strings to test
-   let generator = rand::rng();
    let needle   = generator.gen_str(nlen);
    let haystack = generator.gen_str(hlen);
 
    // run each
    let (sim_val, sim_time)
-      = meow::measure_time_and_value({|| simple(haystack, needle)});
+      = measure_time_and_value(|| simple(&haystack, &needle));
    let (bm_val, bm_time)
-      = meow::measure_time_and_value({|| BM(haystack, needle)});
+      = measure_time_and_value(|| bm(&haystack, &needle));
    let (bmh_val, bmh_time)
-      = meow::measure_time_and_value({|| BMH(haystack, needle)});
+      = measure_time_and_value(|| bmh(&haystack, &needle));
+
+   assert_eq!(sim_val, bm_val);
+   assert_eq!(sim_val, bmh_val);
 
-   assert sim_val == bm_val;
-   assert sim_val == bmh_val;
+   // and against the linear-time searches, which share no tables with them
+   assert_eq!(sim_val, search::knuth_morris_pratt_search(&haystack, &needle,
+                                                        haystack.len(),
+                                                        0, haystack.len()));
+   assert_eq!(sim_val, search::z_algorithm_search(&haystack, &needle,
+                                                 haystack.len(),
+                                                 0, haystack.len()));
 
    // return the ratios
-   ret (sim_time as float / bm_time  as float,
-        sim_time as float / bmh_time as float)
+   (sim_time as f64 / bm_time  as f64,
+    sim_time as f64 / bmh_time as f64)
 }
 
 fn main() {
-/*
-   let loremipsum = "
-Lorem ipsum dolor sit amet, consectetur adipiscing elit. Mauris laoreet consequat fringilla. Quisque venenatis lacinia ipsum non rhoncus. Donec adipiscing fringilla erat, a pretium purus rhoncus quis. Nunc rhoncus dui at ipsum bibendum tincidunt. Etiam eu lorem nulla. Nulla id est id augue blandit vestibulum. Nullam eleifend gravida feugiat. Aliquam vitae urna arcu. Ut sed enim dui, a suscipit sapien.
-
-Ut sollicitudin, metus id malesuada dapibus, quam velit ultrices nibh, at feugiat diam tellus eget risus. Vestibulum in ultrices enim. Nulla at est molestie augue hendrerit tempor sed in purus. Mauris non lorem libero, id faucibus odio. Proin feugiat magna id diam laoreet eu dapibus sapien eleifend. Nunc imperdiet auctor hendrerit. Curabitur porta tempus quam, vel scelerisque elit feugiat ac. Vestibulum feugiat bibendum massa in dapibus. Quisque lacinia porttitor turpis et auctor. Ut quis tortor vitae ligula faucibus euismod nec eu risus. Nullam blandit, risus at consectetur dapibus, velit nisl tincidunt tortor, sed varius risus neque sit amet purus. In diam nisl, facilisis tristique convallis sit amet, posuere congue augue. Vestibulum malesuada, tellus vitae mattis laoreet, nulla felis posuere libero, et blandit eros erat at lacus. Morbi id placerat magna. Nulla lacus magna, gravida sit amet elementum et, posuere nec sapien. Maecenas vitae felis magna, fringilla facilisis massa.
-
-Ut porta, ligula id blandit volutpat, felis sapien ornare sapien, a auctor felis nunc in risus. Etiam in erat at tellus rutrum euismod quis at quam. Proin massa mauris, auctor in tempor eu, cursus quis metus. Suspendisse et dolor quam. Nam id neque eget lorem interdum ultricies. Phasellus aliquet facilisis tortor ac pellentesque. Vestibulum ante ipsum primis in faucibus orci luctus et ultrices posuere cubilia Curae; Suspendisse sed porta purus. Donec consectetur imperdiet tortor et sagittis. Ut pretium vulputate tortor eget ullamcorper. Nullam a urna sollicitudin quam mollis fermentum eu id enim. Mauris pharetra blandit ultricies. In ligula justo, rhoncus et tempus vitae, rutrum non neque.
-
-In accumsan pulvinar lorem sed sollicitudin. Donec justo sem, aliquet at posuere ut, tempus at est. Quisque mattis scelerisque felis ac semper. Quisque nec tristique orci. Donec id diam ut ipsum auctor hendrerit non non sem. Vivamus non erat eros, at vulputate dolor. Duis vehicula, neque sed rhoncus ornare, ante est placerat erat, non luctus justo libero quis mauris. Pellentesque sollicitudin dignissim faucibus. Nunc arcu urna, hendrerit at sagittis nec, dapibus eu leo. Sed eget libero nec risus aliquet hendrerit ullamcorper vel sem. Vestibulum eleifend elit sed tellus tempor elementum. Maecenas eu nibh quis tortor pretium ullamcorper id vel nibh. Pellentesque viverra, nulla vitae condimentum pretium, enim nisl euismod odio, eget vulputate velit justo in lorem.
-
-Nunc eget leo ipsum. Nulla facilisi. Nam adipiscing justo id nisl aliquam at posuere nulla pharetra. Pellentesque fringilla fringilla placerat. Nam pulvinar vehicula augue, a vestibulum turpis rutrum eu. Curabitur semper viverra elit id ultricies. Nunc nibh lacus, congue at imperdiet eget, lacinia at lorem. Aliquam erat volutpat. Pellentesque malesuada ultrices arcu, eu dignissim nisi consectetur at. Nam tristique justo molestie lorem pellentesque vel consectetur augue hendrerit.
-";
-
-
-   meow::time("lorem ipsum ^2 (simple)", {|| BM(loremipsum, loremipsum)} );
-   meow::time("lorem ipsum ^2 (BM)", {|| BM(loremipsum, loremipsum)} );
-*/
+   let mut generator = Rng(0x2545_f491_4f6c_dd1d);
 
+   crossovers(&mut generator);
 
    // 2D range
-   let (num_n, num_h) = (100u, 90u);
-   let (mult_n, mult_h) = (4u, 100u);
-   let row1 = vec::to_mut(vec::from_elem(num_n, 1.0f));
-   let bm_result  = vec::to_mut(vec::from_elem(num_h, row1));
-   let bmh_result = vec::to_mut(vec::from_elem(num_h, row1));
-   let mut nn = 0u;
-   while nn < num_n {
-      let mut hh = 0u;
-      while hh < num_h {
+   let (num_n, num_h) = (100, 90);
+   let (mult_n, mult_h) = (4, 100);
+   let mut bm_result  = vec![vec![1.0; num_n]; num_h];
+   let mut bmh_result = vec![vec![1.0; num_n]; num_h];
+   for nn in 0..num_n {
+      for hh in 0..num_h {
          // save a grid of ratio of time
-         let (bm_ratio, bmh_ratio) = compareHN(1u+hh*mult_h, 1u+nn*mult_n);
+         let (bm_ratio, bmh_ratio)
+            = compare_hn(&mut generator, 1 + hh * mult_h, 1 + nn * mult_n);
          bm_result[hh][nn]  = bm_ratio;
          bmh_result[hh][nn] = bmh_ratio;
-
-         hh += 1u;
       }
-      nn += 1u;
    }
 
    // data [row\y] [col\x]
-   let matlab_data_2d = fn@(data: [mut [mut float]]) -> (str,str,str) {
-      // captured:
-      //let mult_h, mult_n;
+   let matlab_data_2d = |data: &[Vec<f64>]| -> (String, String, String) {
+      let xxlim = data[0].len();
 
-      let xxlim = vec::len(data[0]);
-      let yylim = vec::len(data);
-
-      let mut res = "";
-      let mut xs = "";
-      let mut ys = "";
+      let mut res = String::new();
+      let mut xs = String::new();
+      let mut ys = String::new();
 
       // Y
-      let mut yy = 0u;
-      while yy < yylim {
-         if yy != 0u {
+      for (yy, row) in data.iter().enumerate() {
+         if yy != 0 {
             res += "; ";
             ys += ", ";
          }
 
          // X
-         let mut xx = 0u;
-         while xx < xxlim {
-            if xx != 0u {
+         for (xx, value) in row.iter().enumerate().take(xxlim) {
+            if xx != 0 {
                res += ", ";
-               if yy == 0u {
+               if yy == 0 {
                   xs += ", ";
                }
             }
-            
+
             // data
-            res += #fmt("%f", data[yy][xx]);
+            res += &format!("{:.6}", value);
 
-            if yy == 0u {
-               xs += #fmt("%u", 1u + mult_n * xx);
+            if yy == 0 {
+               xs += &format!("{}", 1 + mult_n * xx);
             }
-            xx += 1u;
          }
 
-         ys += #fmt("%u", 1u + mult_h * yy);
-         yy += 1u;
+         ys += &format!("{}", 1 + mult_h * yy);
       }
 
-      ret (xs, ys, res);
+      (xs, ys, res)
    };
 
-   io::println("######################################################");
-   io::println("### paste into Octave or Matlab... ###################");
+   println!("######################################################");
+   println!("### paste into Octave or Matlab... ###################");
 
    // output to octave/matlab
-   let (xs, ys, ratio) = matlab_data_2d(bm_result);
-   io::println("ratio_bm  = [" + ratio + "];");
-   io::println("needles   = [" + xs + "];");
-   io::println("haystacks = [" + ys + "];");
-
-   io::println("contourf(needles, haystacks, ratio_bm, [0.0 : 0.5 : 3.0]);");
-   io::println("xlabel('needle size');");
-   io::println("ylabel('haystack size');");
-   io::println("title('basic search time / Boyer-Moore time');");
-   io::println("xlim([min(needles) max(needles)]);");
-   io::println("ylim([min(haystacks) max(haystacks)]);");
-   io::println("colormap('cool');");
-   io::println("colorbar;");
-   io::println("");
-   io::println("print('data_bm.svg', '-dSVG');");
-
-   let (xs, ys, ratio) = matlab_data_2d(bmh_result);
-   io::println("ratio_bmh = [" + ratio + "];");
-   io::println("needles   = [" + xs + "];");
-   io::println("haystacks = [" + ys + "];");
-
-   io::println("contourf(needles, haystacks, ratio_bmh, [0.0 : 0.5 : 3.0]);");
-   io::println("xlabel('needle size');");
-   io::println("ylabel('haystack size');");
-   io::println("title('basic search time / Boyer-Moore-Horspool time');");
-   io::println("xlim([min(needles) max(needles)]);");
-   io::println("ylim([min(haystacks) max(haystacks)]);");
-   io::println("colormap('cool');");
-   io::println("colorbar;");
-   io::println("");
-   io::println("print('data_bmh.svg', '-dSVG');");
-
-   io::println("######################################################");
+   let (xs, ys, ratio) = matlab_data_2d(&bm_result);
+   println!("ratio_bm  = [{}];", ratio);
+   println!("needles   = [{}];", xs);
+   println!("haystacks = [{}];", ys);
+   println!("contourf(needles, haystacks, ratio_bm, [0.0 : 0.5 : 3.0]);");
+   println!("xlabel('needle size');");
+   println!("ylabel('haystack size');");
+   println!("title('basic search time / Boyer-Moore time');");
+   println!("xlim([min(needles) max(needles)]);");
+   println!("ylim([min(haystacks) max(haystacks)]);");
+   println!("colormap('cool');");
+   println!("colorbar;");
+   println!();
+   println!("print('data_bm.svg', '-dSVG');");
+
+   let (xs, ys, ratio) = matlab_data_2d(&bmh_result);
+   println!("ratio_bmh = [{}];", ratio);
+   println!("needles   = [{}];", xs);
+   println!("haystacks = [{}];", ys);
+   println!("contourf(needles, haystacks, ratio_bmh, [0.0 : 0.5 : 3.0]);");
+   println!("xlabel('needle size');");
+   println!("ylabel('haystack size');");
+   println!("title('basic search time / Boyer-Moore-Horspool time');");
+   println!("xlim([min(needles) max(needles)]);");
+   println!("ylim([min(haystacks) max(haystacks)]);");
+   println!("colormap('cool');");
+   println!("colorbar;");
+   println!();
+   println!("print('data_bmh.svg', '-dSVG');");
+   println!("######################################################");
 }
-
-
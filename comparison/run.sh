@@ -1 +1 @@
-./comparison > data.m && octave -q data.m && firefox ./data_bm.svg && firefox ./data_bmh.svg
+cargo run --release --example comparison > results/data.m && cd results && octave -q data.m && firefox ./data_bm.svg && firefox ./data_bmh.svg
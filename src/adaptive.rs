@@ -0,0 +1,226 @@
+use super::{boyer_moore_horspool_search, boyer_moore_search, simple_search};
+use super::raita::raita_search;
+use super::sunday::sunday_search;
+use super::two_way::two_way_search;
+use super::error::{check_range, SearchError};
+use super::simd::find_byte;
+
+/// The search algorithms to choose between
+/// (`Thresholds` never chooses Raita or Sunday,
+/// which may still be selected directly)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    Simple,
+    SingleByte,
+    Horspool,
+    BoyerMoore,
+    Raita,
+    Sunday,
+    TwoWay,
+}
+
+impl Algorithm {
+    /// Returns up to `nn` byte positions of matched substrings
+    /// between `start` and `end`, using this algorithm
+    pub fn search(self, haystack: &str, needle: &str,
+                  nn: usize,
+                  start: usize, end: usize) -> Vec<usize> {
+        match self {
+            Algorithm::Simple =>
+                simple_search(haystack, needle, nn, start, end),
+            Algorithm::SingleByte =>
+                single_byte_search(haystack, needle, nn, start, end),
+            Algorithm::Horspool =>
+                boyer_moore_horspool_search(haystack, needle, nn, start, end),
+            Algorithm::BoyerMoore =>
+                boyer_moore_search(haystack, needle, nn, start, end),
+            Algorithm::Raita =>
+                raita_search(haystack, needle, nn, start, end),
+            Algorithm::Sunday =>
+                sunday_search(haystack, needle, nn, start, end),
+            Algorithm::TwoWay =>
+                two_way_search(haystack, needle, nn, start, end),
+        }
+    }
+}
+
+/// The limits used to choose an algorithm.
+///
+/// The defaults come from `comparison/comparison.rs`, on random printable text.
+/// Horspool (with its SIMD prefilter) beat the naive search at every needle length
+/// from two bytes, but only once the haystack passed 128 to 160 bytes,
+/// whatever the needle length, since below that building its table costs more
+/// than it saves. Boyer-Moore never beat Horspool, even on 256-byte needles over
+/// two distinct bytes, so by default it is never chosen.
+/// Two-Way ran 10 to 20 times slower than Horspool, so it is only used
+/// where their tables' memory or quadratic worst case can't be afforded.
+/// Tune them for your own data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Thresholds {
+    /// haystacks shorter than this are searched naively
+    pub min_haystack: usize,
+    /// as are haystacks shorter than this many needle lengths
+    pub min_needle_lengths: usize,
+    /// as are needles shorter than this
+    pub min_needle: usize,
+    /// needles with at most this many distinct bytes use Boyer-Moore...
+    pub boyer_moore_max_alphabet: usize,
+    /// ...if they are at least this long
+    pub boyer_moore_min_needle: usize,
+    /// use Two-Way rather than Horspool or Boyer-Moore,
+    /// for constant space and a linear worst case
+    pub two_way: bool,
+}
+
+impl Default for Thresholds {
+    fn default() -> Thresholds {
+        Thresholds {
+            min_haystack: 160,
+            min_needle_lengths: 1,
+            min_needle: 2,
+            boyer_moore_max_alphabet: 0,
+            boyer_moore_min_needle: 8,
+            two_way: false,
+        }
+    }
+}
+
+impl Thresholds {
+    /// Choose an algorithm for searching `hlen` bytes for `needle`
+    pub fn choose(&self, hlen: usize, needle: &[u8]) -> Algorithm {
+        let nlen = needle.len();
+
+        if nlen == 1 {
+            return Algorithm::SingleByte;
+        }
+
+        if nlen < self.min_needle
+           || hlen < self.min_haystack
+           || hlen < self.min_needle_lengths.saturating_mul(nlen)
+        {
+            return Algorithm::Simple;
+        }
+
+        if self.two_way {
+            return Algorithm::TwoWay;
+        }
+
+        if nlen >= self.boyer_moore_min_needle
+           && alphabet_size(needle) <= self.boyer_moore_max_alphabet
+        {
+            return Algorithm::BoyerMoore;
+        }
+
+        Algorithm::Horspool
+    }
+}
+
+// the number of distinct bytes in `needle`
+fn alphabet_size(needle: &[u8]) -> usize {
+    let mut seen = [false; 256];
+    for &byte in needle {
+        seen[byte as usize] = true;
+    }
+    seen.iter().filter(|&&ss| ss).count()
+}
+
+/// Returns the byte position of the first match, if any
+/// (choosing an algorithm to suit the needle and haystack)
+pub fn find_str(haystack: &str, needle: &str) -> Option<usize> {
+    find_str_between(haystack, needle, 0, haystack.len())
+}
+
+/// Returns the byte position of the first match
+/// between `start` and `end`, if any
+/// (choosing an algorithm to suit the needle and haystack)
+pub fn find_str_between(haystack: &str, needle: &str,
+                        start: usize, end: usize) -> Option<usize> {
+    findn_str_between(haystack, needle, 1, start, end).first().copied()
+}
+
+/// the same, but returning an error for a bad range
+pub fn try_find_str_between(haystack: &str, needle: &str,
+                            start: usize, end: usize)
+                            -> Result<Option<usize>, SearchError> {
+    Ok(try_findn_str_between(haystack, needle, 1, start, end)?.first().copied())
+}
+
+/// Returns up to `nn` byte positions of matched substrings
+/// (choosing an algorithm to suit the needle and haystack)
+pub fn findn_str(haystack: &str, needle: &str, nn: usize) -> Vec<usize> {
+    findn_str_between(haystack, needle, nn, 0, haystack.len())
+}
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`
+/// (choosing an algorithm to suit the needle and haystack)
+pub fn findn_str_between(haystack: &str, needle: &str,
+                         nn: usize,
+                         start: usize, end: usize) -> Vec<usize> {
+    findn_str_between_with(&Thresholds::default(),
+                           haystack, needle, nn, start, end)
+}
+
+/// the same, but returning an error for a bad range
+pub fn try_findn_str_between(haystack: &str, needle: &str,
+                             nn: usize,
+                             start: usize, end: usize)
+                             -> Result<Vec<usize>, SearchError> {
+    check_range(haystack.len(), start, end)?;
+    Ok(findn_str_between(haystack, needle, nn, start, end))
+}
+
+/// the same, but choosing an algorithm with the given thresholds
+pub fn findn_str_between_with(thresholds: &Thresholds,
+                              haystack: &str, needle: &str,
+                              nn: usize,
+                              start: usize, end: usize) -> Vec<usize> {
+    assert!(start <= end);
+
+    thresholds.choose(end - start, needle.as_bytes())
+        .search(haystack, needle, nn, start, end)
+}
+
+/// the same, but returning an error for a bad range
+pub fn try_findn_str_between_with(thresholds: &Thresholds,
+                                  haystack: &str, needle: &str,
+                                  nn: usize,
+                                  start: usize, end: usize)
+                                  -> Result<Vec<usize>, SearchError> {
+    check_range(haystack.len(), start, end)?;
+    Ok(findn_str_between_with(thresholds, haystack, needle, nn, start, end))
+}
+
+/// Returns up to `nn` byte positions of a single-byte needle
+/// between `start` and `end`
+/// (scanning for the byte directly, a block at a time where SIMD is available,
+/// and falling back to Horspool for any other needle)
+pub fn single_byte_search(haystack: &str, needle: &str,
+                          nn: usize,
+                          start: usize, end: usize) -> Vec<usize> {
+    // any other needle has to be searched for in full
+    if needle.len() != 1 {
+        return boyer_moore_horspool_search(haystack, needle, nn, start, end);
+    }
+
+    let haystack = haystack.as_bytes();
+    let needle = needle.as_bytes();
+
+    assert!(start <= end);
+    assert!(end <= haystack.len());
+
+    let mut results = Vec::new();
+
+    let mut from = start;
+    while results.len() < nn {
+        match find_byte(haystack, needle[0], from, end) {
+            Some(found) => {
+                results.push(found);
+                from = found + 1;
+            }
+            None => break,
+        }
+    }
+
+    results
+}
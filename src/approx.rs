@@ -0,0 +1,118 @@
+use super::matches::{Collect, Finder};
+
+/// Returns up to `nn` matches with at most `kk` mismatched bytes
+/// between `start` and `end`, as `(byte position, mismatches)` pairs
+/// (using Tarhio-Ukkonen)
+pub fn tarhio_ukkonen_search<H, N>(haystack: &H, needle: &N,
+                                   kk: usize, nn: usize,
+                                   start: usize, end: usize)
+                                   -> Vec<(usize, usize)>
+    where H: AsRef<[u8]> + ?Sized, N: AsRef<[u8]> + ?Sized
+{
+    TarhioUkkonen::new(needle, kk).findn_between(haystack, nn, start, end)
+}
+
+/// A Tarhio-Ukkonen searcher, for matches with up to `kk` substitutions.
+///
+/// This extends Horspool: each of the last `kk + 1` bytes of the window
+/// has its own bad-character table, and the window shifts by the least
+/// of their shifts, since any match must agree with the needle
+/// on at least one of those bytes.
+/// With `kk` zero this is the Horspool search.
+/// Every window within `kk` mismatches is reported, even where they overlap.
+#[derive(Clone, Debug)]
+pub struct TarhioUkkonen<'n> {
+    needle: &'n [u8],
+    kk: usize,
+    first: usize,       // the first position with a table
+    ct: Vec<Vec<usize>>, // a table for each position from `first`
+}
+
+impl<'n> TarhioUkkonen<'n> {
+    /// Compute the bad-character tables for `needle`
+    /// allowing `kk` mismatches
+    pub fn new<N>(needle: &'n N, kk: usize) -> TarhioUkkonen<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+        let len = needle.len();
+
+        // no tables for an empty needle, which matches immediately
+        if len == 0 {
+            return TarhioUkkonen { needle, kk, first: 0, ct: Vec::new() };
+        }
+
+        // no shift may pass a window with enough matching bytes
+        let longest = len.saturating_sub(kk).max(1);
+        let first = len - 1 - kk.min(len - 1);
+
+        // shift until the unmatched byte lines up with itself in the needle
+        let ct = (first..len)
+            .map(|ii| {
+                let mut deltas = vec![longest; 256];
+                for (jj, &byte) in needle[..ii].iter().enumerate() {
+                    deltas[byte as usize] = (ii - jj).min(longest);
+                }
+                deltas
+            })
+            .collect();
+
+        TarhioUkkonen { needle, kk, first, ct }
+    }
+}
+
+impl Collect for TarhioUkkonen<'_> {
+    type Haystack = [u8];
+    type Match = (usize, usize);
+
+    fn haystack_len(haystack: &[u8]) -> usize {
+        haystack.len()
+    }
+
+    fn collect(&self, haystack: &[u8], nn: usize,
+               start: usize, end: usize) -> Vec<(usize, usize)> {
+        let mut results = Vec::new();
+
+        let nlen = self.needle.len();
+
+        // no matches asked for
+        if nn == 0 {
+            return Vec::new();
+        }
+
+        // empty needle
+        if nlen == 0 {
+            return vec![(start, 0)];
+        }
+
+        // step up through the haystack
+        let mut outerii = start;
+        while outerii + nlen <= end {
+            let window = &haystack[outerii..outerii + nlen];
+
+            // count mismatches, giving up past kk
+            let mut mismatches = 0;
+            for (&ch, &byte) in window.iter().zip(self.needle).rev() {
+                if ch != byte {
+                    mismatches += 1;
+                    if mismatches > self.kk { break; }
+                }
+            }
+
+            if mismatches <= self.kk {
+                results.push((outerii, mismatches));
+
+                if results.len() >= nn { return results; }
+            }
+
+            // the least shift of the last kk + 1 bytes
+            outerii += self.ct.iter()
+                .zip(&window[self.first..])
+                .map(|(deltas, &ch)| deltas[ch as usize])
+                .min()
+                .unwrap_or(1);
+        }
+
+        results
+    }
+}
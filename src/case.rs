@@ -0,0 +1,316 @@
+use std::collections::VecDeque;
+use std::mem;
+use std::str::CharIndices;
+
+use super::{boyer_moore_matching_suffixes, boyer_moore_shift,
+            boyer_moore_shift_by, boyer_moore_unmatched_chars};
+use super::error::{check_range, SearchError};
+use super::matches::{Collect, Finder, Scan, Searcher};
+use super::table::UnmatchedTable;
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`, ignoring ASCII case
+/// (using Boyer-Moore)
+pub fn boyer_moore_search_ignore_ascii_case(haystack: &str, needle: &str,
+                                            nn: usize,
+                                            start: usize, end: usize)
+                                            -> Vec<usize> {
+    BoyerMooreIgnoreAsciiCase::new(needle)
+        .findn_between(haystack, nn, start, end)
+}
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`, ignoring case
+/// (using Boyer-Moore on the case folded needle and haystack)
+pub fn boyer_moore_search_ignore_case(haystack: &str, needle: &str,
+                                      nn: usize,
+                                      start: usize, end: usize) -> Vec<usize> {
+    BoyerMooreIgnoreCase::new(needle).findn_between(haystack, nn, start, end)
+}
+
+/// A Boyer-Moore searcher which ignores ASCII case,
+/// holding the tables computed from its lowercased needle
+#[derive(Clone, Debug)]
+pub struct BoyerMooreIgnoreAsciiCase {
+    needle: Vec<u8>,
+    ct: Vec<usize>,
+    pt: Vec<usize>,
+    overlapping: bool,
+}
+
+impl BoyerMooreIgnoreAsciiCase {
+    /// Compute the Boyer-Moore tables for `needle`, folding ASCII case
+    pub fn new(needle: &str) -> BoyerMooreIgnoreAsciiCase {
+        let needle = needle.as_bytes().to_ascii_lowercase();
+
+        // no tables for an empty needle, which matches immediately
+        if needle.is_empty() {
+            return BoyerMooreIgnoreAsciiCase {
+                needle, ct: Vec::new(), pt: Vec::new(), overlapping: false,
+            };
+        }
+
+        // an uppercase byte in the haystack shifts like its lowercase
+        let mut ct = boyer_moore_unmatched_chars(&needle);
+        for upper in b'A'..=b'Z' {
+            ct[upper as usize] = ct[upper.to_ascii_lowercase() as usize];
+        }
+
+        let pt = boyer_moore_matching_suffixes(&needle);
+
+        BoyerMooreIgnoreAsciiCase { needle, ct, pt, overlapping: false }
+    }
+
+    /// Also report matches which overlap earlier ones,
+    /// shifting by the needle's period after each match
+    pub fn overlapping(mut self, overlapping: bool) -> BoyerMooreIgnoreAsciiCase {
+        self.overlapping = overlapping;
+        self
+    }
+}
+
+impl Scan for BoyerMooreIgnoreAsciiCase {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        let nlen = self.needle.len();
+
+        // step up through the haystack
+        let mut outerii = from;
+        while outerii + nlen <= end {
+
+            // step back through needle
+            let mut windowii = nlen;
+            while 0 < windowii {
+
+                windowii -= 1;
+
+                // matching byte, in either case?
+                let ch = haystack[outerii + windowii];
+                if self.needle[windowii] == ch.to_ascii_lowercase() {
+
+                    // needle fully matched?
+                    if windowii == 0 {
+                        return Some(outerii);
+                    }
+
+                } else {
+                    // no match or a partial match
+                    outerii += boyer_moore_shift(&self.ct, &self.pt, nlen,
+                                                 windowii, ch);
+                    break;
+                }
+            }
+        }
+
+        None
+    }
+
+    fn shift_after_match(&self) -> usize {
+        if self.overlapping {
+            self.pt[self.needle.len() - 1]
+        } else {
+            self.needle.len()
+        }
+    }
+}
+
+/// A Boyer-Moore searcher which ignores case,
+/// comparing the full case folding of the needle and haystack
+/// (so 'ß' matches "ss", and final 'ς' matches 'σ').
+///
+/// Each character is folded by uppercasing then lowercasing it,
+/// which agrees with Unicode's full case folding (`CaseFolding.txt`,
+/// statuses C and F) apart from a few characters, like Cherokee,
+/// which fold to uppercase there.
+/// Only the needle is folded up front; the haystack is folded
+/// a character at a time as the window moves over it.
+///
+/// Matches are reported as byte positions in the original haystack,
+/// and only where they begin and end on whole characters of it.
+#[derive(Clone, Debug)]
+pub struct BoyerMooreIgnoreCase {
+    needle: Vec<char>,
+    ct: Option<UnmatchedTable<char>>,
+    pt: Vec<usize>,
+}
+
+impl BoyerMooreIgnoreCase {
+    /// Compute the Boyer-Moore tables for the case folded `needle`
+    pub fn new(needle: &str) -> BoyerMooreIgnoreCase {
+        let needle: Vec<char> = Folding::new(needle, 0).map(|folded| folded.ch).collect();
+
+        // no tables for an empty needle, which matches immediately
+        if needle.is_empty() {
+            return BoyerMooreIgnoreCase { needle, ct: None, pt: Vec::new() };
+        }
+
+        BoyerMooreIgnoreCase {
+            ct: Some(UnmatchedTable::new(&needle)),
+            pt: boyer_moore_matching_suffixes(&needle),
+            needle,
+        }
+    }
+}
+
+impl Collect for BoyerMooreIgnoreCase {
+    type Haystack = str;
+    type Match = usize;
+
+    fn haystack_len(haystack: &str) -> usize {
+        haystack.len()
+    }
+
+    // the range must lie on character boundaries
+    fn bounds(&self, haystack: &str,
+              start: usize, end: usize)
+              -> Result<Option<(usize, usize)>, SearchError> {
+        check_range(haystack.len(), start, end)?;
+        for offset in [start, end] {
+            if !haystack.is_char_boundary(offset) {
+                return Err(SearchError::NotCharBoundary(offset));
+            }
+        }
+        Ok(Some((start, end)))
+    }
+
+    fn collect(&self, haystack: &str, nn: usize,
+               start: usize, end: usize) -> Vec<usize> {
+        // no matches asked for
+        if nn == 0 {
+            return Vec::new();
+        }
+
+        // empty needle
+        let ct = match self.ct {
+            Some(ref ct) => ct,
+            None => return vec![start],
+        };
+
+        let nlen = self.needle.len();
+        let mut results = Vec::new();
+
+        // the folded characters under the window,
+        // read from the haystack as the window moves onto them
+        let mut folding = Folding::new(&haystack[start..end], start);
+        let mut window = VecDeque::with_capacity(nlen);
+
+        loop {
+            while window.len() < nlen {
+                match folding.next() {
+                    Some(folded) => window.push_back(folded),
+                    None => return results,
+                }
+            }
+
+            // step back through needle
+            let mut windowii = nlen;
+            let shift = loop {
+                // needle fully matched?
+                if windowii == 0 {
+                    break None;
+                }
+                windowii -= 1;
+
+                // no match or a partial match
+                let ch = window[windowii].ch;
+                if self.needle[windowii] != ch {
+                    break Some(boyer_moore_shift_by(ct.get(ch), &self.pt, nlen,
+                                                    windowii));
+                }
+            };
+
+            let shift = match shift {
+                Some(shift) => shift,
+
+                // a match of whole characters of the haystack,
+                // after which the folding carries on with the next one
+                None if window[0].first && window[nlen - 1].last => {
+                    results.push(window[0].offset);
+
+                    if results.len() >= nn { return results; }
+
+                    window.clear();
+                    continue;
+                }
+
+                // a match beginning or ending inside the folding
+                // of a single character of the haystack
+                None => self.pt[nlen - 1],
+            };
+
+            // move the window, reading past its end if need be
+            for _ in 0..shift {
+                if window.pop_front().is_none() && folding.next().is_none() {
+                    return results;
+                }
+            }
+        }
+    }
+}
+
+// a character of the case folded haystack
+struct Folded {
+    ch: char,
+    // the byte offset of the haystack character it came from
+    offset: usize,
+    // whether it begins or ends that character's folding
+    first: bool,
+    last: bool,
+}
+
+// the case folding of `text`, one folded character at a time,
+// with offsets counted from `base`
+struct Folding<'t> {
+    chars: CharIndices<'t>,
+    base: usize,
+    // the rest of the folding of the current character, reversed
+    pending: Vec<char>,
+    offset: usize,
+    first: bool,
+}
+
+impl<'t> Folding<'t> {
+    fn new(text: &'t str, base: usize) -> Folding<'t> {
+        Folding {
+            chars: text.char_indices(),
+            base,
+            pending: Vec::new(),
+            offset: base,
+            first: false,
+        }
+    }
+}
+
+impl Iterator for Folding<'_> {
+    type Item = Folded;
+
+    fn next(&mut self) -> Option<Folded> {
+        if self.pending.is_empty() {
+            let (offset, ch) = self.chars.next()?;
+            self.offset = self.base + offset;
+
+            // ASCII folds to one character
+            if ch.is_ascii() {
+                return Some(Folded {
+                    ch: ch.to_ascii_lowercase(), offset: self.offset,
+                    first: true, last: true,
+                });
+            }
+
+            self.pending.extend(ch.to_uppercase().flat_map(char::to_lowercase));
+            self.pending.reverse();
+            self.first = true;
+        }
+
+        let ch = self.pending.pop()?;
+        Some(Folded {
+            ch,
+            offset: self.offset,
+            first: mem::replace(&mut self.first, false),
+            last: self.pending.is_empty(),
+        })
+    }
+}
@@ -0,0 +1,238 @@
+use std::str::Bytes;
+
+use super::matches::Scan;
+
+/// A set of bytes, which one position of a `ClassPattern` accepts
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ByteSet {
+    bits: [u64; 4],
+}
+
+impl ByteSet {
+    /// The set of no bytes
+    pub fn new() -> ByteSet {
+        ByteSet::default()
+    }
+
+    /// The set of every byte
+    pub fn any() -> ByteSet {
+        ByteSet { bits: [u64::MAX; 4] }
+    }
+
+    /// The set of just `byte`
+    pub fn byte(byte: u8) -> ByteSet {
+        let mut set = ByteSet::new();
+        set.insert(byte);
+        set
+    }
+
+    /// The set of bytes from `lo` to `hi` inclusive
+    pub fn range(lo: u8, hi: u8) -> ByteSet {
+        let mut set = ByteSet::new();
+        for byte in lo..=hi {
+            set.insert(byte);
+        }
+        set
+    }
+
+    /// Add `byte` to the set
+    pub fn insert(&mut self, byte: u8) {
+        self.bits[byte as usize / 64] |= 1 << (byte % 64);
+    }
+
+    /// Whether `byte` is in the set
+    pub fn contains(&self, byte: u8) -> bool {
+        self.bits[byte as usize / 64] & (1 << (byte % 64)) != 0
+    }
+
+    /// The bytes in either set
+    pub fn union(&self, other: &ByteSet) -> ByteSet {
+        let mut bits = self.bits;
+        for (bb, oo) in bits.iter_mut().zip(other.bits) {
+            *bb |= oo;
+        }
+        ByteSet { bits }
+    }
+
+    /// The bytes in the set, in order
+    pub fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(move |&byte| self.contains(byte))
+    }
+}
+
+/// A needle in which each position accepts a set of bytes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClassPattern {
+    classes: Vec<ByteSet>,
+}
+
+impl ClassPattern {
+    /// A pattern of byte sets
+    pub fn new(classes: Vec<ByteSet>) -> ClassPattern {
+        ClassPattern { classes }
+    }
+
+    /// Parse a pattern of literal bytes and bracketed classes
+    /// of bytes and ranges, such as `"[0-9][0-9]:[0-5][0-9] [aApP]m"`,
+    /// with `\` escaping the byte after it.
+    /// A `-` first or last in a class is literal, as in `"[+-]"`.
+    ///
+    /// Classes are sets of bytes, so a multibyte character
+    /// can't appear within brackets: `None` is returned for any
+    /// non-ASCII byte there (outside them it matches byte by byte).
+    pub fn parse(pattern: &str) -> Option<ClassPattern> {
+        let mut bytes = pattern.bytes();
+        let mut classes = Vec::new();
+
+        while let Some(byte) = bytes.next() {
+            let class = match byte {
+                b'\\' => ByteSet::byte(bytes.next()?),
+                b'[' => {
+                    let mut set = ByteSet::new();
+                    let mut prev = None;
+                    loop {
+                        match class_byte(&mut bytes)? {
+                            b']' => break,
+                            b'-' if prev.is_some() => {
+                                let hi = match class_byte(&mut bytes)? {
+                                    b'\\' => class_byte(&mut bytes)?,
+                                    // a trailing dash is literal
+                                    b']' => {
+                                        set.insert(b'-');
+                                        break;
+                                    }
+                                    hi => hi,
+                                };
+                                let lo = prev.take()?;
+                                if hi < lo { return None; }
+                                set = set.union(&ByteSet::range(lo, hi));
+                            }
+                            member => {
+                                let member = if member == b'\\' {
+                                    class_byte(&mut bytes)?
+                                } else {
+                                    member
+                                };
+                                set.insert(member);
+                                prev = Some(member);
+                            }
+                        }
+                    }
+                    set
+                }
+                b']' => return None,
+                _ => ByteSet::byte(byte),
+            };
+            classes.push(class);
+        }
+
+        Some(ClassPattern { classes })
+    }
+
+    /// The byte sets of the pattern
+    pub fn classes(&self) -> &[ByteSet] {
+        &self.classes
+    }
+
+    /// The number of positions in the pattern
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    /// Whether the pattern has no positions
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+}
+
+// the next byte within brackets, if it's ASCII
+fn class_byte(bytes: &mut Bytes) -> Option<u8> {
+    bytes.next().filter(u8::is_ascii)
+}
+
+/// A Boyer-Moore-Horspool searcher for a needle of byte sets,
+/// shifting only as far as every byte each position accepts allows
+#[derive(Clone, Debug)]
+pub struct ClassHorspool<'p> {
+    pattern: &'p [ByteSet],
+    ct: Vec<usize>,
+    overlapping: bool,
+}
+
+impl<'p> ClassHorspool<'p> {
+    /// Compute the Boyer-Moore-Horspool table for `pattern`
+    pub fn new(pattern: &'p ClassPattern) -> ClassHorspool<'p> {
+        let pattern = pattern.classes();
+
+        // no table for an empty needle, which matches immediately
+        if pattern.is_empty() {
+            return ClassHorspool { pattern, ct: Vec::new(), overlapping: false };
+        }
+
+        ClassHorspool {
+            pattern,
+            ct: class_unmatched_chars(pattern),
+            overlapping: false,
+        }
+    }
+
+    /// Also report matches which overlap earlier ones
+    pub fn overlapping(mut self, overlapping: bool) -> ClassHorspool<'p> {
+        self.overlapping = overlapping;
+        self
+    }
+}
+
+impl Scan for ClassHorspool<'_> {
+    fn needle_len(&self) -> usize {
+        self.pattern.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        let nlen = self.pattern.len();
+
+        // step up through the haystack
+        let mut outerii = from;
+        while outerii + nlen <= end {
+            let window = &haystack[outerii..outerii + nlen];
+
+            // check the window from the end
+            if window.iter().zip(self.pattern).rev()
+                .all(|(&ch, class)| class.contains(ch))
+            {
+                return Some(outerii);
+            }
+
+            // shift on the window's last byte
+            outerii += self.ct[window[nlen - 1] as usize];
+        }
+
+        None
+    }
+
+    fn shift_after_match(&self) -> usize {
+        // the least shift for any byte the last position accepts
+        if self.overlapping {
+            let last = self.pattern[self.pattern.len() - 1];
+            last.bytes().map(|byte| self.ct[byte as usize]).min().unwrap_or(1)
+        } else {
+            self.pattern.len()
+        }
+    }
+}
+
+// the bad-character table, where each position is an occurrence
+// of every byte in its set
+fn class_unmatched_chars(pattern: &[ByteSet]) -> Vec<usize> {
+    let len = pattern.len();
+    let mut deltas = vec![len; 256];
+
+    // from first to last-1, so later positions give smaller shifts
+    for (jj, class) in pattern[..len - 1].iter().enumerate() {
+        for byte in class.bytes() {
+            deltas[byte as usize] = len - 1 - jj;
+        }
+    }
+
+    deltas
+}
@@ -0,0 +1,39 @@
+use std::error::Error;
+use std::fmt;
+
+/// Why a search could not be made
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchError {
+    /// `start` is after `end`, or `end` is past the end of the haystack
+    InvalidRange { start: usize, end: usize, len: usize },
+    /// the needle is empty, where a table must be computed from it
+    EmptyNeedle,
+    /// this offset is within a multibyte character
+    NotCharBoundary(usize),
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            SearchError::InvalidRange { start, end, len } =>
+                write!(f, "invalid range {}..{} of a haystack of {} bytes",
+                       start, end, len),
+            SearchError::EmptyNeedle =>
+                write!(f, "empty needle"),
+            SearchError::NotCharBoundary(offset) =>
+                write!(f, "byte offset {} is not on a char boundary", offset),
+        }
+    }
+}
+
+impl Error for SearchError {}
+
+// the errors for which the search functions would assert
+pub(crate) fn check_range(len: usize,
+                          start: usize, end: usize) -> Result<(), SearchError> {
+    if start <= end && end <= len {
+        Ok(())
+    } else {
+        Err(SearchError::InvalidRange { start, end, len })
+    }
+}
@@ -0,0 +1,120 @@
+use std::hash::Hash;
+
+use super::{boyer_moore_matching_suffixes, boyer_moore_shift_by};
+use super::matches::{Collect, Finder};
+use super::table::{HashedTable, ShiftTable, Symbol, UnmatchedTable};
+
+/// Returns up to `nn` positions of matched subslices
+/// between `start` and `end`
+/// (using Boyer-Moore over any hashable symbols)
+pub fn generic_boyer_moore_search<T: Eq + Hash>(haystack: &[T], needle: &[T],
+                                                nn: usize,
+                                                start: usize, end: usize)
+                                                -> Vec<usize> {
+    GenericBoyerMoore::new(needle).findn_between(haystack, nn, start, end)
+}
+
+/// A Boyer-Moore searcher over slices of any symbols,
+/// holding the good-suffix table and a bad-character table `C`
+/// computed from its needle
+#[derive(Clone, Debug)]
+pub struct GenericBoyerMoore<'n, T, C = HashedTable<'n, T>> {
+    needle: &'n [T],
+    ct: Option<C>,
+    pt: Vec<usize>,
+}
+
+impl<'n, T: Eq + Hash> GenericBoyerMoore<'n, T> {
+    /// Compute the Boyer-Moore tables for `needle`,
+    /// with the bad-character table in a hash map
+    pub fn new(needle: &'n [T]) -> GenericBoyerMoore<'n, T> {
+        // no tables for an empty needle, which matches immediately
+        if needle.is_empty() {
+            return GenericBoyerMoore { needle, ct: None, pt: Vec::new() };
+        }
+
+        GenericBoyerMoore {
+            needle,
+            ct: Some(HashedTable::new(needle)),
+            pt: boyer_moore_matching_suffixes(needle),
+        }
+    }
+}
+
+impl<'n, T: Symbol> GenericBoyerMoore<'n, T, UnmatchedTable<T>> {
+    /// Compute the Boyer-Moore tables for `needle`,
+    /// with the bad-character table in an array if the alphabet is small
+    pub fn with_symbols(needle: &'n [T]) -> GenericBoyerMoore<'n, T, UnmatchedTable<T>> {
+        // no tables for an empty needle, which matches immediately
+        if needle.is_empty() {
+            return GenericBoyerMoore { needle, ct: None, pt: Vec::new() };
+        }
+
+        GenericBoyerMoore {
+            needle,
+            ct: Some(UnmatchedTable::new(needle)),
+            pt: boyer_moore_matching_suffixes(needle),
+        }
+    }
+}
+
+impl<T: Eq, C: ShiftTable<T>> Collect for GenericBoyerMoore<'_, T, C> {
+    type Haystack = [T];
+    type Match = usize;
+
+    fn haystack_len(haystack: &[T]) -> usize {
+        haystack.len()
+    }
+
+    fn collect(&self, haystack: &[T], nn: usize,
+               start: usize, end: usize) -> Vec<usize> {
+        let mut results = Vec::new();
+
+        let nlen = self.needle.len();
+
+        // no matches asked for
+        if nn == 0 {
+            return Vec::new();
+        }
+
+        // empty needle
+        let ct = match self.ct {
+            Some(ref ct) => ct,
+            None => return vec![start],
+        };
+
+        // step up through the haystack
+        let mut outerii = start;
+        while outerii + nlen <= end {
+
+            // step back through needle
+            let mut windowii = nlen;
+            while 0 < windowii {
+
+                windowii -= 1;
+
+                // matching symbol?
+                if self.needle[windowii] == haystack[outerii + windowii] {
+
+                    // needle fully matched?
+                    if windowii == 0 {
+                        results.push(outerii);
+
+                        if results.len() >= nn { return results; }
+
+                        outerii += nlen;
+                    }
+
+                } else {
+                    // no match or a partial match
+                    let raw_char_shift = ct.shift(&haystack[outerii + windowii]);
+                    outerii += boyer_moore_shift_by(raw_char_shift, &self.pt,
+                                                    nlen, windowii);
+                    break;
+                }
+            }
+        }
+
+        results
+    }
+}
@@ -0,0 +1,218 @@
+use std::io::{self, Read};
+
+use super::matches::{Resume, Scan, Searcher};
+
+// how much to read from a reader at once
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`
+/// (using Knuth-Morris-Pratt, in one pass over the haystack)
+pub fn knuth_morris_pratt_search(haystack: &str, needle: &str,
+                                 nn: usize,
+                                 start: usize, end: usize) -> Vec<usize> {
+    knuth_morris_pratt_search_bytes(haystack, needle, nn, start, end)
+}
+
+/// the same, but over any bytes
+pub fn knuth_morris_pratt_search_bytes<H, N>(haystack: &H, needle: &N,
+                                             nn: usize,
+                                             start: usize, end: usize)
+                                             -> Vec<usize>
+    where H: AsRef<[u8]> + ?Sized, N: AsRef<[u8]> + ?Sized
+{
+    KnuthMorrisPratt::new(needle).findn_between(haystack, nn, start, end)
+}
+
+/// the same, but also reporting matches which overlap earlier ones
+pub fn knuth_morris_pratt_search_overlapping(haystack: &str, needle: &str,
+                                             nn: usize,
+                                             start: usize, end: usize)
+                                             -> Vec<usize> {
+    KnuthMorrisPratt::new(needle).overlapping(true)
+        .findn_between(haystack, nn, start, end)
+}
+
+/// Compute the length of the longest proper prefix of `needle`
+/// which is also a suffix of each of its prefixes
+/// (a.k.a. the prefix function, or failure table)
+pub fn knuth_morris_pratt_borders<T: Eq>(needle: &[T]) -> Vec<usize> {
+    let mut borders = vec![0; needle.len()];
+
+    // the border of the prefix before ii
+    let mut border = 0;
+    for ii in 1..needle.len() {
+        // fall back through shorter borders until one extends
+        while border > 0 && needle[ii] != needle[border] {
+            border = borders[border - 1];
+        }
+        if needle[ii] == needle[border] {
+            border += 1;
+        }
+        borders[ii] = border;
+    }
+
+    borders
+}
+
+/// A Knuth-Morris-Pratt searcher.
+///
+/// Each byte of the haystack is read once, in order:
+/// on a mismatch the needle falls back to the longest prefix
+/// which still matches, rather than the haystack stepping back,
+/// so the search never takes more than twice the haystack's length.
+#[derive(Clone, Debug)]
+pub struct KnuthMorrisPratt<'n> {
+    needle: &'n [u8],
+    borders: Vec<usize>,
+    overlapping: bool,
+}
+
+impl<'n> KnuthMorrisPratt<'n> {
+    /// Compute the failure table for `needle`,
+    /// which may be a `str` or any other bytes
+    pub fn new<N>(needle: &'n N) -> KnuthMorrisPratt<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+
+        KnuthMorrisPratt {
+            needle,
+            borders: knuth_morris_pratt_borders(needle),
+            overlapping: false,
+        }
+    }
+
+    /// Also report matches which overlap earlier ones,
+    /// shifting by the needle's period after each match
+    pub fn overlapping(mut self, overlapping: bool) -> KnuthMorrisPratt<'n> {
+        self.overlapping = overlapping;
+        self
+    }
+
+    /// Returns a search over a stream of bytes, fed in chunks,
+    /// which keeps nothing from one chunk to the next
+    /// but how much of the needle matched at its end
+    pub fn stream(&self) -> KnuthMorrisPrattStream<'_, 'n> {
+        KnuthMorrisPrattStream { searcher: self, matched: 0, offset: 0, started: false }
+    }
+
+    // how much of the needle matches after `byte`,
+    // given how much matched before it
+    fn step(&self, mut matched: usize, byte: u8) -> usize {
+        // fall back through shorter borders until one extends
+        while matched > 0 && byte != self.needle[matched] {
+            matched = self.borders[matched - 1];
+        }
+        if byte == self.needle[matched] {
+            matched += 1;
+        }
+        matched
+    }
+
+    // how much of the needle still matches after a full match
+    fn matched_after_match(&self) -> usize {
+        if self.overlapping { self.borders[self.needle.len() - 1] } else { 0 }
+    }
+}
+
+impl Scan for KnuthMorrisPratt<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        self.resume(haystack, from, end, &mut Resume::default())
+    }
+
+    fn shift_after_match(&self) -> usize {
+        // the needle's period, keeping its longest border
+        let nlen = self.needle.len();
+        if self.overlapping { nlen - self.borders[nlen - 1] } else { nlen }
+    }
+
+    fn resume(&self, haystack: &[u8], from: usize, end: usize,
+              state: &mut Resume) -> Option<usize> {
+        let nlen = self.needle.len();
+
+        // how much of the needle matches the bytes before ii,
+        // carried over from the last match
+        let mut matched = state.known;
+
+        // step forward through the haystack, once
+        for (ii, &byte) in haystack.iter().enumerate().take(end).skip(from + matched) {
+            matched = self.step(matched, byte);
+            if matched == nlen {
+                state.known = self.matched_after_match();
+                return Some(ii + 1 - nlen);
+            }
+        }
+
+        None
+    }
+}
+
+/// A Knuth-Morris-Pratt search over a stream of bytes, fed in chunks.
+///
+/// Each byte is read once, as it's fed, and matches are reported
+/// as absolute byte offsets within the whole stream.
+pub struct KnuthMorrisPrattStream<'s, 'n> {
+    searcher: &'s KnuthMorrisPratt<'n>,
+    matched: usize,
+    offset: usize, // absolute offset of the next byte
+    started: bool,
+}
+
+impl KnuthMorrisPrattStream<'_, '_> {
+    /// Search the next chunk of the stream,
+    /// returning the offsets of matches which end within it
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<usize> {
+        let mut results = Vec::new();
+
+        let nlen = self.searcher.needle.len();
+
+        // empty needle, matching once at the start
+        if nlen == 0 {
+            if !self.started {
+                results.push(0);
+            }
+            self.started = true;
+            return results;
+        }
+        self.started = true;
+
+        for &byte in chunk {
+            self.offset += 1;
+            self.matched = self.searcher.step(self.matched, byte);
+            if self.matched == nlen {
+                results.push(self.offset - nlen);
+                self.matched = self.searcher.matched_after_match();
+            }
+        }
+
+        results
+    }
+
+    /// Search everything remaining in `reader`,
+    /// returning the offsets of all matches
+    pub fn read_from<R: Read>(&mut self, mut reader: R) -> io::Result<Vec<usize>> {
+        let mut results = Vec::new();
+        let mut chunk = vec![0; CHUNK_SIZE];
+
+        loop {
+            let read = match reader.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+
+            results.extend(self.feed(&chunk[..read]));
+        }
+
+        // an empty stream still matches an empty needle
+        results.extend(self.feed(&[]));
+
+        Ok(results)
+    }
+}
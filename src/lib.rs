@@ -0,0 +1,745 @@
+//! An implementation of the Boyer-Moore search algorithm in Rust
+
+use std::sync::OnceLock;
+
+mod adaptive;
+mod approx;
+mod case;
+mod class;
+mod error;
+mod generic;
+mod kmp;
+mod linear;
+mod matches;
+mod multi;
+mod raita;
+mod rare;
+mod replace;
+mod reverse;
+mod simd;
+mod split;
+mod stream;
+mod sunday;
+mod table;
+mod two_way;
+mod utf8;
+mod wildcard;
+mod z;
+
+pub use adaptive::{find_str, find_str_between, findn_str, findn_str_between,
+                   findn_str_between_with, single_byte_search,
+                   try_find_str_between, try_findn_str_between,
+                   try_findn_str_between_with,
+                   Algorithm, Thresholds};
+pub use approx::{tarhio_ukkonen_search, TarhioUkkonen};
+pub use case::{boyer_moore_search_ignore_ascii_case, boyer_moore_search_ignore_case,
+               BoyerMooreIgnoreAsciiCase, BoyerMooreIgnoreCase};
+pub use class::{ByteSet, ClassHorspool, ClassPattern};
+pub use error::SearchError;
+use error::check_range;
+pub use generic::{generic_boyer_moore_search, GenericBoyerMoore};
+pub use kmp::{knuth_morris_pratt_borders, knuth_morris_pratt_search,
+              knuth_morris_pratt_search_bytes, knuth_morris_pratt_search_overlapping,
+              KnuthMorrisPratt, KnuthMorrisPrattStream};
+pub use linear::{apostolico_giancarlo_search, turbo_boyer_moore_search,
+                 ApostolicoGiancarlo, TurboBoyerMoore};
+pub use matches::{Finder, Matches, Searcher};
+pub use multi::{wu_manber_search, WuManber};
+use matches::Scan;
+pub use raita::{raita_search, raita_search_bytes, raita_search_overlapping, Raita};
+pub use rare::{ByteFrequencies, RareByte};
+pub use replace::{replace, replace_between, replacen};
+pub use reverse::{boyer_moore_horspool_reverse_search, boyer_moore_reverse_search,
+                  ReverseBoyerMoore, ReverseHorspool, ReverseSearcher};
+pub use split::{RSplit, Split};
+pub use stream::StreamSearch;
+pub use sunday::{sunday_search, sunday_search_bytes, sunday_search_overlapping,
+                 Sunday};
+pub use table::{HashedTable, ShiftTable, Symbol, UnmatchedTable};
+pub use two_way::{two_way_search, two_way_search_bytes, two_way_search_overlapping,
+                  TwoWay};
+pub use utf8::{Position, StrSearcher};
+pub use wildcard::{WildcardBoyerMoore, WildcardPattern};
+pub use z::{z_algorithm_prefixes, z_algorithm_search, z_algorithm_search_bytes,
+            z_algorithm_search_overlapping, ZAlgorithm};
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`
+/// (using a naive search algorithm)
+pub fn simple_search(haystack: &str, needle: &str,
+                     nn: usize,
+                     start: usize, end: usize) -> Vec<usize> {
+    simple_search_bytes(haystack, needle, nn, start, end)
+}
+
+/// the same, but over any bytes
+pub fn simple_search_bytes<H, N>(haystack: &H, needle: &N,
+                                 nn: usize,
+                                 start: usize, end: usize) -> Vec<usize>
+    where H: AsRef<[u8]> + ?Sized, N: AsRef<[u8]> + ?Sized
+{
+    simple(haystack.as_ref(), needle.as_ref(), nn, start, end, false)
+}
+
+/// the same, but also reporting matches which overlap earlier ones
+pub fn simple_search_overlapping(haystack: &str, needle: &str,
+                                 nn: usize,
+                                 start: usize, end: usize) -> Vec<usize> {
+    simple(haystack.as_bytes(), needle.as_bytes(), nn, start, end, true)
+}
+
+/// the same as `simple_search`, but returning an error for a bad range
+pub fn try_simple_search(haystack: &str, needle: &str,
+                         nn: usize,
+                         start: usize, end: usize)
+                         -> Result<Vec<usize>, SearchError> {
+    check_range(haystack.len(), start, end)?;
+    Ok(simple_search(haystack, needle, nn, start, end))
+}
+
+fn simple(haystack: &[u8], needle: &[u8],
+          nn: usize,
+          start: usize, end: usize,
+          overlapping: bool) -> Vec<usize> {
+    let mut results = Vec::new();
+
+    let nlen = needle.len();
+
+    assert!(start <= end);
+    assert!(end <= haystack.len());
+    let hlen = end - start;
+
+    // no matches asked for
+    if nn == 0 {
+        return Vec::new();
+    }
+
+    // empty needle
+    if nlen == 0 {
+        return vec![start];
+    }
+
+    // haystack empty, or smaller than needle
+    if hlen == 0 || hlen < nlen {
+        return Vec::new();
+    }
+
+    let (mut ii, mut match_start, mut match_i) = (start, 0, 0);
+
+    while ii < end {
+        if haystack[ii] == needle[match_i] {
+            if match_i == 0 { match_start = ii; }
+            match_i += 1;
+            // Found a match
+            if match_i == nlen {
+                results.push(match_start);
+                match_i = 0;
+
+                if results.len() >= nn { return results; }
+
+                // restart just after the start of this match
+                if overlapping {
+                    ii = match_start + 1;
+                    continue;
+                }
+            }
+            ii += 1;
+        } else {
+            // Failed match, backtrack
+            if match_i > 0 {
+                match_i = 0;
+                ii = match_start + 1;
+            } else {
+                ii += 1;
+            }
+        }
+    }
+
+    results
+}
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`
+/// (using Boyer-Moore)
+pub fn boyer_moore_search(haystack: &str, needle: &str,
+                          nn: usize,
+                          start: usize, end: usize) -> Vec<usize> {
+    boyer_moore_search_bytes(haystack, needle, nn, start, end)
+}
+
+/// the same, but over any bytes
+pub fn boyer_moore_search_bytes<H, N>(haystack: &H, needle: &N,
+                                      nn: usize,
+                                      start: usize, end: usize) -> Vec<usize>
+    where H: AsRef<[u8]> + ?Sized, N: AsRef<[u8]> + ?Sized
+{
+    BoyerMoore::new(needle).findn_between(haystack, nn, start, end)
+}
+
+/// the same, but also reporting matches which overlap earlier ones
+pub fn boyer_moore_search_overlapping(haystack: &str, needle: &str,
+                                      nn: usize,
+                                      start: usize, end: usize) -> Vec<usize> {
+    BoyerMoore::new(needle).overlapping(true)
+        .findn_between(haystack, nn, start, end)
+}
+
+/// the same as `boyer_moore_search`, but returning an error for a bad range
+pub fn try_boyer_moore_search(haystack: &str, needle: &str,
+                              nn: usize,
+                              start: usize, end: usize)
+                              -> Result<Vec<usize>, SearchError> {
+    BoyerMoore::new(needle).try_findn_between(haystack, nn, start, end)
+}
+
+/// A Boyer-Moore searcher, holding the tables computed from its needle
+/// so they can be reused across many haystacks
+#[derive(Clone, Debug)]
+pub struct BoyerMoore<'n> {
+    needle: &'n [u8],
+    ct: Vec<usize>,
+    pt: Vec<usize>,
+    overlapping: bool,
+}
+
+impl<'n> BoyerMoore<'n> {
+    /// Compute the Boyer-Moore tables for `needle`,
+    /// which may be a `str` or any other bytes
+    pub fn new<N>(needle: &'n N) -> BoyerMoore<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+
+        // no tables for an empty needle, which matches immediately
+        if needle.is_empty() {
+            return BoyerMoore {
+                needle, ct: Vec::new(), pt: Vec::new(), overlapping: false,
+            };
+        }
+
+        BoyerMoore {
+            needle,
+            ct: boyer_moore_unmatched_chars(needle),
+            pt: boyer_moore_matching_suffixes(needle),
+            overlapping: false,
+        }
+    }
+
+    /// Also report matches which overlap earlier ones,
+    /// shifting by the needle's period after each match
+    pub fn overlapping(mut self, overlapping: bool) -> BoyerMoore<'n> {
+        self.overlapping = overlapping;
+        self
+    }
+
+    /// Returns a copy of `haystack` with every match replaced by `with`;
+    /// if overlapping, each run of overlapping matches is replaced once
+    /// (and an empty needle matches at every byte)
+    pub fn replace<H, W>(&self, haystack: &H, with: &W) -> Vec<u8>
+        where H: AsRef<[u8]> + ?Sized, W: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        self.replacen(haystack, with, haystack.len() + 1)
+    }
+
+    /// the same, but replacing up to `nn` matches
+    pub fn replacen<H, W>(&self, haystack: &H, with: &W, nn: usize) -> Vec<u8>
+        where H: AsRef<[u8]> + ?Sized, W: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        self.replace_between(haystack, with, nn, 0, haystack.len())
+    }
+
+    /// the same, but replacing up to `nn` matches
+    /// between `start` and `end`
+    pub fn replace_between<H, W>(&self, haystack: &H, with: &W, nn: usize,
+                                 start: usize, end: usize) -> Vec<u8>
+        where H: AsRef<[u8]> + ?Sized, W: AsRef<[u8]> + ?Sized
+    {
+        replace::replaced(self, haystack.as_ref(), with.as_ref(), nn, start, end)
+    }
+
+    /// Replace every match within `buffer` by `with`,
+    /// returning the number of replacements
+    pub fn replace_in_place<W>(&self, buffer: &mut Vec<u8>, with: &W) -> usize
+        where W: AsRef<[u8]> + ?Sized
+    {
+        replace::replace_in_place(self, buffer, with.as_ref())
+    }
+
+    /// Returns a search over a stream of bytes, fed in chunks
+    pub fn stream(&self) -> StreamSearch<'_> {
+        StreamSearch::new(self)
+    }
+}
+
+impl Scan for BoyerMoore<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        let nlen = self.needle.len();
+
+        // step up through the haystack
+        let mut outerii = from;
+        while outerii + nlen <= end {
+
+            // step back through needle
+            // (checking outer range again)
+            let mut windowii = nlen;
+            while 0 < windowii {
+
+                windowii -= 1;
+
+                // matching byte?
+                if self.needle[windowii] == haystack[outerii + windowii] {
+
+                    // needle fully matched?
+                    // note: last decremented windowii
+                    if windowii == 0 {
+                        return Some(outerii);
+                    }
+
+                    // if not fully matched, leave outerii alone
+                    // but decrement the windowii
+
+                } else {
+                    // no match or a partial match
+                    outerii += boyer_moore_shift(&self.ct, &self.pt, nlen,
+                                                 windowii,
+                                                 haystack[outerii + windowii]);
+                    break;
+                }
+            }
+        }
+
+        None
+    }
+
+    fn shift_after_match(&self) -> usize {
+        // the good-suffix shift for a full match
+        // is the period of the needle
+        if self.overlapping {
+            self.pt[self.needle.len() - 1]
+        } else {
+            self.needle.len()
+        }
+    }
+}
+
+// query both tables based on position
+// within the needle and character in haystack
+fn boyer_moore_shift(ct: &[usize], pt: &[usize], nlen: usize,
+                     pos: usize, ch: u8) -> usize {
+    boyer_moore_shift_by(ct[ch as usize], pt, nlen, pos)
+}
+
+// the same, given the bad-character table's entry for the character
+fn boyer_moore_shift_by(raw_char_shift: usize, pt: &[usize], nlen: usize,
+                        pos: usize) -> usize {
+    let matched_so_far = nlen - 1 - pos;
+    let pref_shift     = pt[matched_so_far];
+
+    if raw_char_shift >= matched_so_far {
+        let adj_char_shift = raw_char_shift - matched_so_far;
+
+        if adj_char_shift > pref_shift {
+            return adj_char_shift;
+        }
+    }
+
+    pref_shift
+}
+
+/// Compute the table used to choose a shift based on
+/// an unmatched character's possible position within the search string
+/// (a.k.a. the bad-character table)
+pub fn boyer_moore_unmatched_chars(needle: &[u8]) -> Vec<usize> {
+    let len = needle.len();
+    let mut deltas = vec![len; 256];
+
+    assert!(0 < len);
+    let mut jj = len - 1; // drop the last byte
+
+    // from last-1 to first
+    while jj > 0 {
+        jj -= 1;
+
+        let key = needle[jj] as usize;
+
+        // if we haven't set it yet, set it now
+        // (besides default)
+        if deltas[key] == len {
+            deltas[key] = len - 1 - jj;
+        }
+    }
+
+    deltas
+}
+
+/// the same, but returning an error for an empty needle
+pub fn try_boyer_moore_unmatched_chars(needle: &[u8])
+                                       -> Result<Vec<usize>, SearchError> {
+    if needle.is_empty() {
+        return Err(SearchError::EmptyNeedle);
+    }
+    Ok(boyer_moore_unmatched_chars(needle))
+}
+
+/// For each prefix of the search string
+/// find the largest suffix which is a suffix of the search string
+/// (comparing bytes, or any other symbols)
+pub fn boyer_moore_largest_suffixes<T: Eq>(needle: &[T]) -> Vec<usize> {
+    let len = needle.len();
+
+    if len == 0 { return Vec::new(); }
+
+    let mut suffs = vec![0; len];
+    suffs[len - 1] = len;
+
+    let mut ii   = len - 1;
+    let mut head = len; // index starting the previous found suffix
+    let mut tail = len; // index after the previous found suffix
+
+    // loop through each smaller prefix,
+    // keeping track of the last suffix of a prefix
+    // which was found to be a suffix of the needle
+    while 0 < ii {
+        ii -= 1;
+
+        if head < ii + 1
+           && suffs[(len - 1) - ((tail - 1) - ii)] + head < ii + 1
+        {
+            // The needle is a suffix of itself, stored before this loop,
+            // so each prefix of that is matched
+            // with its largest possible suffix...
+            //
+            // So (bear with me) when considering prefixes
+            // of another matched prefix (i.e., when head <= ii < tail)
+            // if the corresponding maximum prefix's match is
+            // smaller than the space left within the current match,
+            // then we know this prefix's matching suffix is the same.
+
+            // Consider:
+            //     01234567
+            //     heyyheyy
+            //       ^   ^
+            //
+            // When testing i=2, a match from 0-3 has already been found
+            // ("heyy"), and the match at i=6 ("y") fits
+            // in the remaining space within the current match,
+            // we know that suffs[2]=sufs[6].
+            //
+            // If, however, sufs[6] was much larger, we'd have to work more.
+
+            suffs[ii] = suffs[(len - 1) - ((tail - 1) - ii)];
+
+        } else {
+            // Here, find the largest suffix of the needle which matches
+            // the prefix ending at ii.
+
+            // move the head left
+            //
+            // Note that if the head is already further left,
+            // we've already explored that far and eliminated the possibility
+            // of smaller match, above.
+            if ii < head {
+                head = ii + 1;
+            }
+
+            // put the tail here (the ending of this suffix)
+            tail = ii + 1;
+
+            // move the head left until it is before the matching suffix
+            while 1 <= head
+               && needle[head - 1] == needle[(len - 1) - (tail - head)]
+            {
+                head -= 1;
+            }
+
+            // store the length of this suffix
+            suffs[ii] = tail - head;
+        }
+    }
+
+    suffs
+}
+
+/// Compute the table used to choose a shift based on
+/// a partially matched suffix of the search string
+/// (a.k.a. the good-suffix table)
+pub fn boyer_moore_matching_suffixes<T: Eq>(needle: &[T]) -> Vec<usize> {
+    let len   = needle.len();
+
+    // compute the largest suffix of each prefix
+    let suffs = boyer_moore_largest_suffixes(needle);
+
+    // (1) initialize deltas
+    let mut deltas = vec![len; len];
+
+    // (2) step to smaller suffixes ending with ii, and
+    // if a whole prefix is a suffix
+    // set all the deltas for indexes smaller than length - 1 - ii
+    // to length - 1 - ii
+    let mut ii = len;
+    let mut jj = 0;
+    while 0 < ii {
+        ii -= 1;
+
+        if suffs[ii] == ii + 1 {
+            // do not reset jj, only do this once
+            while ii < len - 1 - jj {
+                if deltas[len - 1 - jj] == len {
+                    deltas[len - 1 - jj] = len - 1 - ii;
+                }
+                jj += 1;
+            }
+        }
+    }
+
+    // (3) then for each different matched suffix size, set the delta
+    let mut kk = 0;
+    while 2 <= len && kk <= len - 2 {
+        deltas[suffs[kk]] = len - 1 - kk;
+        kk += 1;
+    }
+
+    deltas
+}
+
+
+/// the same, but Boyer-Moore-Horspool
+pub fn boyer_moore_horspool_search(haystack: &str, needle: &str,
+                                   nn: usize,
+                                   start: usize, end: usize) -> Vec<usize> {
+    boyer_moore_horspool_search_bytes(haystack, needle, nn, start, end)
+}
+
+/// the same, but over any bytes
+pub fn boyer_moore_horspool_search_bytes<H, N>(haystack: &H, needle: &N,
+                                               nn: usize,
+                                               start: usize, end: usize)
+                                               -> Vec<usize>
+    where H: AsRef<[u8]> + ?Sized, N: AsRef<[u8]> + ?Sized
+{
+    Horspool::new(needle).findn_between(haystack, nn, start, end)
+}
+
+/// the same, but also reporting matches which overlap earlier ones
+pub fn boyer_moore_horspool_search_overlapping(haystack: &str, needle: &str,
+                                               nn: usize,
+                                               start: usize, end: usize)
+                                               -> Vec<usize> {
+    Horspool::new(needle).overlapping(true)
+        .findn_between(haystack, nn, start, end)
+}
+
+/// the same as `boyer_moore_horspool_search`,
+/// but returning an error for a bad range
+pub fn try_boyer_moore_horspool_search(haystack: &str, needle: &str,
+                                       nn: usize,
+                                       start: usize, end: usize)
+                                       -> Result<Vec<usize>, SearchError> {
+    Horspool::new(needle).try_findn_between(haystack, nn, start, end)
+}
+
+/// A Boyer-Moore-Horspool searcher, holding the table computed from its
+/// needle so it can be reused across many haystacks
+#[derive(Clone, Debug)]
+pub struct Horspool<'n> {
+    needle: &'n [u8],
+    ct: Vec<usize>,
+    overlapping: bool,
+    simd: bool,
+    reversed: OnceLock<ReverseHorspool<'n>>, // for rsplit, built on first use
+}
+
+impl<'n> Horspool<'n> {
+    /// Compute the Boyer-Moore-Horspool table for `needle`,
+    /// which may be a `str` or any other bytes
+    pub fn new<N>(needle: &'n N) -> Horspool<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+
+        // no table for an empty needle, which matches immediately
+        if needle.is_empty() {
+            return Horspool {
+                needle, ct: Vec::new(), overlapping: false, simd: true,
+                reversed: OnceLock::new(),
+            };
+        }
+
+        Horspool {
+            needle,
+            ct: boyer_moore_unmatched_chars(needle),
+            overlapping: false,
+            simd: true,
+            reversed: OnceLock::new(),
+        }
+    }
+
+    /// Also report matches which overlap earlier ones,
+    /// shifting by the last byte's bad-character entry after each match
+    pub fn overlapping(mut self, overlapping: bool) -> Horspool<'n> {
+        self.overlapping = overlapping;
+        self
+    }
+
+    /// Whether to find candidates with SSE2 or AVX2, where available,
+    /// before checking them byte by byte (the default)
+    pub fn simd(mut self, simd: bool) -> Horspool<'n> {
+        self.simd = simd;
+        self
+    }
+
+    /// Returns a copy of `haystack` with every match replaced by `with`;
+    /// if overlapping, each run of overlapping matches is replaced once
+    /// (and an empty needle matches at every byte)
+    pub fn replace<H, W>(&self, haystack: &H, with: &W) -> Vec<u8>
+        where H: AsRef<[u8]> + ?Sized, W: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        self.replacen(haystack, with, haystack.len() + 1)
+    }
+
+    /// the same, but replacing up to `nn` matches
+    pub fn replacen<H, W>(&self, haystack: &H, with: &W, nn: usize) -> Vec<u8>
+        where H: AsRef<[u8]> + ?Sized, W: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        self.replace_between(haystack, with, nn, 0, haystack.len())
+    }
+
+    /// the same, but replacing up to `nn` matches
+    /// between `start` and `end`
+    pub fn replace_between<H, W>(&self, haystack: &H, with: &W, nn: usize,
+                                 start: usize, end: usize) -> Vec<u8>
+        where H: AsRef<[u8]> + ?Sized, W: AsRef<[u8]> + ?Sized
+    {
+        replace::replaced(self, haystack.as_ref(), with.as_ref(), nn, start, end)
+    }
+
+    /// Replace every match within `buffer` by `with`,
+    /// returning the number of replacements
+    pub fn replace_in_place<W>(&self, buffer: &mut Vec<u8>, with: &W) -> usize
+        where W: AsRef<[u8]> + ?Sized
+    {
+        replace::replace_in_place(self, buffer, with.as_ref())
+    }
+
+    /// Returns an iterator over the pieces of `haystack` between matches;
+    /// the needle must not be empty
+    pub fn split<'s, 'h, H>(&'s self, haystack: &'h H) -> Split<'h, 's>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        Split::new(self, haystack.as_ref(), usize::MAX, false)
+    }
+
+    /// the same, but returning at most `nn` pieces,
+    /// the last holding the rest of `haystack`
+    pub fn splitn<'s, 'h, H>(&'s self, haystack: &'h H, nn: usize) -> Split<'h, 's>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        Split::new(self, haystack.as_ref(), nn, false)
+    }
+
+    /// the same, but each piece ending with the match after it
+    pub fn split_inclusive<'s, 'h, H>(&'s self, haystack: &'h H) -> Split<'h, 's>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        Split::new(self, haystack.as_ref(), usize::MAX, true)
+    }
+
+    /// the same, but last piece first,
+    /// using the table mirrored for a reverse search
+    /// (computed on the first call, and kept for the next)
+    pub fn rsplit<'s, 'h, H>(&'s self, haystack: &'h H) -> RSplit<'h, 's>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        let reversed = self.reversed.get_or_init(|| ReverseHorspool::new(self.needle));
+        RSplit::new(reversed, self.needle.len(), haystack.as_ref())
+    }
+
+    /// Returns a search over a stream of bytes, fed in chunks
+    pub fn stream(&self) -> StreamSearch<'_> {
+        StreamSearch::new(self)
+    }
+}
+
+impl Scan for Horspool<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        let nlen = self.needle.len();
+
+        // filter whole blocks of windows first,
+        // leaving the rest to the byte by byte loop
+        let mut outerii = from;
+        if self.simd {
+            if let Some(found) = simd::prefilter(haystack, self.needle,
+                                                 &mut outerii, end) {
+                return Some(found);
+            }
+        }
+
+        // step up through the haystack
+        while outerii + nlen <= end {
+
+            // step back through needle
+            // (checking outer range again)
+            let mut windowii = nlen;
+            while 0 < windowii {
+
+                windowii -= 1;
+
+                // matching byte?
+                if self.needle[windowii] == haystack[outerii + windowii] {
+
+                    // needle fully matched?
+                    // note: last decremented windowii
+                    if windowii == 0 {
+                        return Some(outerii);
+                    }
+
+                    // if not fully matched, leave outerii alone
+                    // but decrement the windowii
+
+                } else {
+                    // no match or a partial match
+                    outerii += horspool_shift(&self.ct, nlen,
+                                              windowii,
+                                              haystack[outerii + windowii]);
+                    break;
+                }
+            }
+        }
+
+        None
+    }
+
+    fn shift_after_match(&self) -> usize {
+        // shift as if the last byte had mismatched
+        if self.overlapping {
+            self.ct[self.needle[self.needle.len() - 1] as usize]
+        } else {
+            self.needle.len()
+        }
+    }
+}
+
+// query the bad-character table based on position
+// within the needle and character in haystack
+fn horspool_shift(ct: &[usize], nlen: usize, pos: usize, ch: u8) -> usize {
+    let matched_so_far = nlen - 1 - pos;
+    let raw_char_shift = ct[ch as usize];
+
+    if raw_char_shift >= matched_so_far {
+        return raw_char_shift - matched_so_far;
+    }
+
+    1
+}
@@ -0,0 +1,296 @@
+use super::{boyer_moore_largest_suffixes, boyer_moore_matching_suffixes,
+            boyer_moore_unmatched_chars};
+use super::matches::{Resume, Scan, Searcher};
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`
+/// (using Turbo Boyer-Moore, which is linear in the worst case)
+pub fn turbo_boyer_moore_search(haystack: &str, needle: &str,
+                                nn: usize,
+                                start: usize, end: usize) -> Vec<usize> {
+    TurboBoyerMoore::new(needle).findn_between(haystack, nn, start, end)
+}
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`
+/// (using Apostolico-Giancarlo, which is linear in the worst case)
+pub fn apostolico_giancarlo_search(haystack: &str, needle: &str,
+                                   nn: usize,
+                                   start: usize, end: usize) -> Vec<usize> {
+    ApostolicoGiancarlo::new(needle).findn_between(haystack, nn, start, end)
+}
+
+/// A Turbo Boyer-Moore searcher.
+///
+/// After a good-suffix shift it remembers the segment of the needle
+/// which matched the haystack, and jumps over it at the next attempt,
+/// so no byte of the haystack is compared more than twice.
+#[derive(Clone, Debug)]
+pub struct TurboBoyerMoore<'n> {
+    needle: &'n [u8],
+    ct: Vec<usize>,
+    pt: Vec<usize>,
+    overlapping: bool,
+}
+
+impl<'n> TurboBoyerMoore<'n> {
+    /// Compute the Boyer-Moore tables for `needle`,
+    /// which may be a `str` or any other bytes
+    pub fn new<N>(needle: &'n N) -> TurboBoyerMoore<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+
+        // no tables for an empty needle, which matches immediately
+        if needle.is_empty() {
+            return TurboBoyerMoore {
+                needle, ct: Vec::new(), pt: Vec::new(), overlapping: false,
+            };
+        }
+
+        TurboBoyerMoore {
+            needle,
+            ct: boyer_moore_unmatched_chars(needle),
+            pt: boyer_moore_matching_suffixes(needle),
+            overlapping: false,
+        }
+    }
+
+    /// Also report matches which overlap earlier ones,
+    /// shifting by the needle's period after each match
+    pub fn overlapping(mut self, overlapping: bool) -> TurboBoyerMoore<'n> {
+        self.overlapping = overlapping;
+        self
+    }
+}
+
+impl Scan for TurboBoyerMoore<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        self.resume(haystack, from, end, &mut Resume::default())
+    }
+
+    fn shift_after_match(&self) -> usize {
+        if self.overlapping { self.pt[self.needle.len() - 1] } else { self.needle.len() }
+    }
+
+    fn resume(&self, haystack: &[u8], from: usize, end: usize,
+              state: &mut Resume) -> Option<usize> {
+        let nlen = self.needle.len();
+
+        // the length of the segment matched at the previous attempt,
+        // which now lies `shift` bytes further into the needle
+        // (carried over from the last match)
+        let mut memory = state.known;
+        let mut shift = if memory != 0 { self.shift_after_match() } else { nlen };
+
+        // step up through the haystack
+        let mut outerii = from;
+        while outerii + nlen <= end {
+
+            // step back through needle, with windowii bytes left to check,
+            // jumping over the remembered segment
+            let mut windowii = nlen;
+            while 0 < windowii
+                  && self.needle[windowii - 1] == haystack[outerii + windowii - 1]
+            {
+                windowii -= 1;
+
+                if memory != 0 && windowii + shift == nlen {
+                    windowii -= memory;
+                }
+            }
+
+            // needle fully matched?
+            if windowii == 0 {
+                state.known = nlen - self.shift_after_match();
+                return Some(outerii);
+            }
+
+            // no match or a partial match
+            let matched_so_far = nlen - windowii;
+            let ch = haystack[outerii + windowii - 1];
+
+            let good_suffix_shift = self.pt[matched_so_far];
+            let turbo_shift = memory as isize - matched_so_far as isize;
+            let char_shift = self.ct[ch as usize] as isize
+                             - matched_so_far as isize;
+
+            shift = good_suffix_shift
+                .max(turbo_shift.max(char_shift).max(0) as usize);
+
+            if shift == good_suffix_shift {
+                // remember the suffix matched this time
+                memory = (nlen - shift).min(matched_so_far);
+            } else {
+                // shift past the remembered segment
+                if turbo_shift < char_shift {
+                    shift = shift.max(memory + 1);
+                }
+                memory = 0;
+            }
+
+            outerii += shift;
+        }
+
+        None
+    }
+}
+
+/// An Apostolico-Giancarlo searcher.
+///
+/// It remembers, for each position of the current window,
+/// the length of any suffix of the needle matched there at earlier attempts,
+/// and uses the largest suffixes table to skip over those segments
+/// without comparing them again.
+#[derive(Clone, Debug)]
+pub struct ApostolicoGiancarlo<'n> {
+    needle: &'n [u8],
+    ct: Vec<usize>,
+    pt: Vec<usize>,
+    suffs: Vec<usize>,
+    overlapping: bool,
+}
+
+impl<'n> ApostolicoGiancarlo<'n> {
+    /// Compute the Boyer-Moore tables and largest suffixes for `needle`,
+    /// which may be a `str` or any other bytes
+    pub fn new<N>(needle: &'n N) -> ApostolicoGiancarlo<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+
+        // no tables for an empty needle, which matches immediately
+        if needle.is_empty() {
+            return ApostolicoGiancarlo {
+                needle,
+                ct: Vec::new(),
+                pt: Vec::new(),
+                suffs: Vec::new(),
+                overlapping: false,
+            };
+        }
+
+        ApostolicoGiancarlo {
+            needle,
+            ct: boyer_moore_unmatched_chars(needle),
+            pt: boyer_moore_matching_suffixes(needle),
+            suffs: boyer_moore_largest_suffixes(needle),
+            overlapping: false,
+        }
+    }
+
+    /// Also report matches which overlap earlier ones,
+    /// shifting by the needle's period after each match
+    pub fn overlapping(mut self, overlapping: bool) -> ApostolicoGiancarlo<'n> {
+        self.overlapping = overlapping;
+        self
+    }
+}
+
+impl Scan for ApostolicoGiancarlo<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        self.resume(haystack, from, end, &mut Resume::default())
+    }
+
+    fn shift_after_match(&self) -> usize {
+        if self.overlapping { self.pt[self.needle.len() - 1] } else { self.needle.len() }
+    }
+
+    fn resume(&self, haystack: &[u8], from: usize, end: usize,
+              state: &mut Resume) -> Option<usize> {
+        let nlen = self.needle.len();
+
+        // for each position of the window, the length of the match
+        // which ended there at an earlier attempt (or zero),
+        // in a ring indexed by the position in the haystack,
+        // carried over from the last match
+        let skip = &mut state.scratch;
+        if skip.is_empty() {
+            skip.resize(nlen, 0);
+        }
+
+        // step up through the haystack
+        let mut outerii = from;
+        while outerii + nlen <= end {
+
+            // step back through needle, with windowii bytes left to check
+            let mut windowii = nlen;
+            while 0 < windowii {
+                let ii = windowii - 1;
+                let remembered = skip[(outerii + ii) % nlen];
+                let suffix = self.suffs[ii];
+
+                if remembered > 0 {
+                    if remembered > suffix {
+                        // the needle's suffix here is shorter than the match,
+                        // so either the whole needle is matched
+                        // or there's a mismatch just before it
+                        if suffix == ii + 1 {
+                            windowii = 0;
+                        } else {
+                            windowii -= suffix;
+                        }
+                        break;
+                    }
+
+                    // jump over the remembered match,
+                    // stopping if it was shorter than the needle's suffix
+                    windowii -= remembered;
+                    if remembered < suffix {
+                        break;
+                    }
+
+                } else if self.needle[ii] == haystack[outerii + ii] {
+                    windowii -= 1;
+                } else {
+                    break;
+                }
+            }
+
+            let shift;
+
+            // needle fully matched?
+            let found = windowii == 0;
+            if found {
+                skip[(outerii + nlen - 1) % nlen] = nlen;
+                shift = self.shift_after_match();
+
+            } else {
+                // no match or a partial match
+                let matched_so_far = nlen - windowii;
+                let ch = haystack[outerii + windowii - 1];
+
+                skip[(outerii + nlen - 1) % nlen] = matched_so_far;
+
+                let char_shift = self.ct[ch as usize].saturating_sub(matched_so_far);
+                shift = self.pt[matched_so_far].max(char_shift);
+            }
+
+            // forget the matches which the window moves past,
+            // whose places in the ring are taken by the bytes it moves onto
+            if shift < nlen {
+                for ii in outerii + nlen..outerii + nlen + shift {
+                    skip[ii % nlen] = 0;
+                }
+            } else {
+                skip.fill(0);
+            }
+
+            if found {
+                return Some(outerii);
+            }
+
+            outerii += shift;
+        }
+
+        None
+    }
+}
@@ -0,0 +1,256 @@
+use std::iter::FusedIterator;
+
+use super::error::{check_range, SearchError};
+
+// the scanning step of a searcher,
+// which an iterator can resume after each match
+// (public only so `Searcher` can build on it, but not nameable outside)
+pub trait Scan {
+    fn needle_len(&self) -> usize;
+
+    // returns the first match starting at or after `from`
+    // and ending before `end`
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize>;
+
+    // how far to move past the start of a match
+    fn shift_after_match(&self) -> usize;
+
+    // the same as `scan`, for searchers which carry what they learnt
+    // before one match into the search for the next
+    fn resume(&self, haystack: &[u8], from: usize, end: usize,
+              state: &mut Resume) -> Option<usize> {
+        let _ = state;
+        self.scan(haystack, from, end)
+    }
+}
+
+// what a searcher carries from one match to the next within a search
+#[derive(Clone, Debug, Default)]
+pub struct Resume {
+    // how many bytes at the start of the next window
+    // are already known to match the needle
+    pub(crate) known: usize,
+    // anything else a searcher keeps, allocated once per search
+    pub(crate) scratch: Vec<usize>,
+}
+
+/// An iterator over the byte positions of matches,
+/// which resumes the search from the last match on each call to `next`
+pub struct Matches<'h, 'n> {
+    searcher: &'n dyn Scan,
+    haystack: &'h [u8],
+    position: usize,
+    end: usize,
+    state: Resume,
+    done: bool,
+}
+
+impl<'h, 'n> Matches<'h, 'n> {
+    pub(crate) fn new(searcher: &'n dyn Scan, haystack: &'h [u8],
+                      start: usize, end: usize) -> Matches<'h, 'n> {
+        assert!(start <= end);
+        assert!(end <= haystack.len());
+
+        Matches {
+            searcher, haystack, position: start, end,
+            state: Resume::default(), done: false,
+        }
+    }
+}
+
+impl Iterator for Matches<'_, '_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.done {
+            return None;
+        }
+
+        // empty needle, matching once at the start
+        if self.searcher.needle_len() == 0 {
+            self.done = true;
+            return Some(self.position);
+        }
+
+        match self.searcher.resume(self.haystack, self.position, self.end,
+                                   &mut self.state) {
+            Some(found) => {
+                self.position = found + self.searcher.shift_after_match();
+                Some(found)
+            }
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+impl FusedIterator for Matches<'_, '_> {}
+
+/// The methods shared by the single-needle searchers,
+/// all built on their scanning step.
+///
+/// Bring it into scope (`use search::Searcher;`) to call them.
+pub trait Searcher: Scan + Sized {
+    /// Returns the byte position of the first match, if any
+    fn find<H>(&self, haystack: &H) -> Option<usize>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        self.find_between(haystack, 0, haystack.len())
+    }
+
+    /// Returns the byte position of the first match
+    /// between `start` and `end`, if any
+    fn find_between<H>(&self, haystack: &H,
+                       start: usize, end: usize) -> Option<usize>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        self.matches_between(haystack, start, end).next()
+    }
+
+    /// Returns the byte positions of all matches
+    fn find_all<H>(&self, haystack: &H) -> Vec<usize>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        self.matches(haystack).collect()
+    }
+
+    /// Returns up to `nn` byte positions of matches
+    fn find_n<H>(&self, haystack: &H, nn: usize) -> Vec<usize>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        self.findn_between(haystack, nn, 0, haystack.len())
+    }
+
+    /// Returns up to `nn` byte positions of matches
+    /// between `start` and `end`
+    fn findn_between<H>(&self, haystack: &H, nn: usize,
+                        start: usize, end: usize) -> Vec<usize>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        self.matches_between(haystack, start, end).take(nn).collect()
+    }
+
+    /// the same, but returning an error for a bad range
+    fn try_findn_between<H>(&self, haystack: &H, nn: usize,
+                            start: usize, end: usize)
+                            -> Result<Vec<usize>, SearchError>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        check_range(haystack.len(), start, end)?;
+        Ok(self.findn_between(haystack, nn, start, end))
+    }
+
+    /// Returns an iterator over the byte positions of matches
+    fn matches<'s, 'h, H>(&'s self, haystack: &'h H) -> Matches<'h, 's>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        self.matches_between(haystack, 0, haystack.len())
+    }
+
+    /// Returns an iterator over the byte positions of matches
+    /// between `start` and `end`, scanning lazily
+    fn matches_between<'s, 'h, H>(&'s self, haystack: &'h H,
+                                  start: usize, end: usize)
+                                  -> Matches<'h, 's>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        Matches::new(self, haystack.as_ref(), start, end)
+    }
+}
+
+impl<S: Scan> Searcher for S {}
+
+// the search of a searcher which collects its matches in one pass,
+// rather than scanning for them one at a time
+// (public only so `Finder` can build on it, but not nameable outside)
+pub trait Collect {
+    type Haystack: ?Sized;
+    type Match;
+
+    fn haystack_len(haystack: &Self::Haystack) -> usize;
+
+    // checks the range, returning the one to search
+    // (none if nothing in it can match)
+    fn bounds(&self, haystack: &Self::Haystack,
+              start: usize, end: usize)
+              -> Result<Option<(usize, usize)>, SearchError> {
+        check_range(Self::haystack_len(haystack), start, end)?;
+        Ok(Some((start, end)))
+    }
+
+    // returns up to `nn` matches between `start` and `end`,
+    // which `bounds` has already checked
+    fn collect(&self, haystack: &Self::Haystack, nn: usize,
+               start: usize, end: usize) -> Vec<Self::Match>;
+}
+
+/// The methods shared by the searchers which collect their matches
+/// in one pass, rather than scanning for them one at a time.
+///
+/// Bring it into scope (`use search::Finder;`) to call them.
+pub trait Finder: Collect + Sized {
+    /// Returns the first match, if any
+    fn find<H>(&self, haystack: &H) -> Option<Self::Match>
+        where H: AsRef<Self::Haystack> + ?Sized
+    {
+        let len = Self::haystack_len(haystack.as_ref());
+        self.find_between(haystack, 0, len)
+    }
+
+    /// Returns the first match
+    /// between `start` and `end`, if any
+    fn find_between<H>(&self, haystack: &H,
+                       start: usize, end: usize) -> Option<Self::Match>
+        where H: AsRef<Self::Haystack> + ?Sized
+    {
+        self.findn_between(haystack, 1, start, end).into_iter().next()
+    }
+
+    /// Returns all matches
+    fn find_all<H>(&self, haystack: &H) -> Vec<Self::Match>
+        where H: AsRef<Self::Haystack> + ?Sized
+    {
+        self.find_n(haystack, usize::MAX)
+    }
+
+    /// Returns up to `nn` matches
+    fn find_n<H>(&self, haystack: &H, nn: usize) -> Vec<Self::Match>
+        where H: AsRef<Self::Haystack> + ?Sized
+    {
+        let len = Self::haystack_len(haystack.as_ref());
+        self.findn_between(haystack, nn, 0, len)
+    }
+
+    /// Returns up to `nn` matches
+    /// between `start` and `end`
+    fn findn_between<H>(&self, haystack: &H, nn: usize,
+                        start: usize, end: usize) -> Vec<Self::Match>
+        where H: AsRef<Self::Haystack> + ?Sized
+    {
+        match self.try_findn_between(haystack, nn, start, end) {
+            Ok(results) => results,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// the same, but returning an error for a bad range
+    fn try_findn_between<H>(&self, haystack: &H, nn: usize,
+                            start: usize, end: usize)
+                            -> Result<Vec<Self::Match>, SearchError>
+        where H: AsRef<Self::Haystack> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        Ok(match self.bounds(haystack, start, end)? {
+            Some((start, end)) => self.collect(haystack, nn, start, end),
+            None => Vec::new(),
+        })
+    }
+}
+
+impl<S: Collect> Finder for S {}
@@ -0,0 +1,140 @@
+use super::error::SearchError;
+use super::matches::{Collect, Finder};
+
+/// Returns up to `nn` matches of any of `needles`
+/// between `start` and `end`, as `(needle index, byte position)` pairs
+/// in haystack order
+/// (using Wu-Manber)
+pub fn wu_manber_search<H, N>(haystack: &H, needles: &[N],
+                              nn: usize,
+                              start: usize, end: usize) -> Vec<(usize, usize)>
+    where H: AsRef<[u8]> + ?Sized, N: AsRef<[u8]>
+{
+    WuManber::new(needles).findn_between(haystack.as_ref(), nn, start, end)
+}
+
+/// A Wu-Manber searcher for several needles at once.
+///
+/// Windows as long as the shortest needle step through the haystack,
+/// shifting on the hash of the block of (up to) two bytes which ends them,
+/// by the least distance from that block to the end of the window
+/// in any needle (the SHIFT table).
+/// Where that distance is zero the needles whose window ends with the block
+/// (the HASH table) are checked, first by the hash of their first block
+/// (the PREFIX table) and then in full.
+/// Every occurrence of every needle is reported, even where they overlap.
+/// The needles must not be empty.
+#[derive(Clone, Debug)]
+pub struct WuManber<'n> {
+    needles: Vec<&'n [u8]>,
+    window: usize,
+    block: usize,
+    shift: Vec<usize>,
+    candidates: Vec<Vec<usize>>,
+    prefixes: Vec<usize>,
+}
+
+// the number of entries in the SHIFT and HASH tables
+const TABLE_SIZE: usize = 1 << 12;
+
+// hash a block of bytes into the tables
+fn hash(block: &[u8]) -> usize {
+    block.iter().fold(0, |hash, &byte| hash << 4 ^ byte as usize) & (TABLE_SIZE - 1)
+}
+
+impl<'n> WuManber<'n> {
+    /// Compute the shared tables for `needles`,
+    /// which may be `str`s or any other bytes
+    pub fn new<N: AsRef<[u8]>>(needles: &'n [N]) -> WuManber<'n> {
+        let needles: Vec<&[u8]> = needles.iter().map(|nn| nn.as_ref()).collect();
+
+        let window = needles.iter().map(|nn| nn.len()).min().unwrap_or(0);
+        assert!(needles.is_empty() || 0 < window);
+
+        // blocks of two bytes, unless a needle is only one byte long
+        let block = window.min(2);
+
+        // the least shift over the windows of all the needles,
+        // defaulting to past any block which appears in none of them
+        let mut shift = vec![window + 1 - block; TABLE_SIZE];
+        let mut candidates = vec![Vec::new(); TABLE_SIZE];
+        let mut prefixes = Vec::with_capacity(needles.len());
+        for (id, needle) in needles.iter().enumerate() {
+            for last in block - 1..window {
+                let delta = &mut shift[hash(&needle[last + 1 - block..=last])];
+                *delta = (window - 1 - last).min(*delta);
+            }
+
+            // the needles to check when a window ends with this block
+            candidates[hash(&needle[window - block..window])].push(id);
+            prefixes.push(hash(&needle[..block]));
+        }
+
+        WuManber { needles, window, block, shift, candidates, prefixes }
+    }
+
+    /// the same, but returning an error if any needle is empty
+    pub fn try_new<N: AsRef<[u8]>>(needles: &'n [N]) -> Result<WuManber<'n>, SearchError> {
+        if needles.iter().any(|nn| nn.as_ref().is_empty()) {
+            return Err(SearchError::EmptyNeedle);
+        }
+        Ok(WuManber::new(needles))
+    }
+}
+
+impl Collect for WuManber<'_> {
+    type Haystack = [u8];
+    type Match = (usize, usize);
+
+    fn haystack_len(haystack: &[u8]) -> usize {
+        haystack.len()
+    }
+
+    fn collect(&self, haystack: &[u8], nn: usize,
+               start: usize, end: usize) -> Vec<(usize, usize)> {
+        let mut results = Vec::new();
+
+        // no matches asked for
+        if nn == 0 {
+            return Vec::new();
+        }
+
+        // no needles
+        if self.needles.is_empty() {
+            return results;
+        }
+
+        // step up through the haystack
+        let mut outerii = start;
+        while outerii + self.window <= end {
+            let windowend = outerii + self.window;
+            let last = hash(&haystack[windowend - self.block..windowend]);
+
+            let shift = self.shift[last];
+            if shift > 0 {
+                outerii += shift;
+                continue;
+            }
+
+            // check each needle whose window ends with this block,
+            // and begins with the same one as the haystack's
+            let first = hash(&haystack[outerii..outerii + self.block]);
+            for &id in &self.candidates[last] {
+                let needle = self.needles[id];
+
+                if self.prefixes[id] == first
+                   && outerii + needle.len() <= end
+                   && &haystack[outerii..outerii + needle.len()] == needle
+                {
+                    results.push((id, outerii));
+
+                    if results.len() >= nn { return results; }
+                }
+            }
+
+            outerii += 1;
+        }
+
+        results
+    }
+}
@@ -0,0 +1,109 @@
+use super::boyer_moore_unmatched_chars;
+use super::matches::{Scan, Searcher};
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`
+/// (using Raita)
+pub fn raita_search(haystack: &str, needle: &str,
+                    nn: usize,
+                    start: usize, end: usize) -> Vec<usize> {
+    raita_search_bytes(haystack, needle, nn, start, end)
+}
+
+/// the same, but over any bytes
+pub fn raita_search_bytes<H, N>(haystack: &H, needle: &N,
+                                nn: usize,
+                                start: usize, end: usize) -> Vec<usize>
+    where H: AsRef<[u8]> + ?Sized, N: AsRef<[u8]> + ?Sized
+{
+    Raita::new(needle).findn_between(haystack, nn, start, end)
+}
+
+/// the same, but also reporting matches which overlap earlier ones
+pub fn raita_search_overlapping(haystack: &str, needle: &str,
+                                nn: usize,
+                                start: usize, end: usize) -> Vec<usize> {
+    Raita::new(needle).overlapping(true)
+        .findn_between(haystack, nn, start, end)
+}
+
+/// A Raita searcher.
+///
+/// This shifts like Horspool, but compares each window's last byte,
+/// then its first, then its middle, and only then the rest,
+/// since neighbouring bytes of text tend to match or fail together.
+#[derive(Clone, Debug)]
+pub struct Raita<'n> {
+    needle: &'n [u8],
+    ct: Vec<usize>,
+    overlapping: bool,
+}
+
+impl<'n> Raita<'n> {
+    /// Compute the Boyer-Moore-Horspool table for `needle`,
+    /// which may be a `str` or any other bytes
+    pub fn new<N>(needle: &'n N) -> Raita<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+
+        // no table for an empty needle, which matches immediately
+        if needle.is_empty() {
+            return Raita { needle, ct: Vec::new(), overlapping: false };
+        }
+
+        Raita {
+            needle,
+            ct: boyer_moore_unmatched_chars(needle),
+            overlapping: false,
+        }
+    }
+
+    /// Also report matches which overlap earlier ones,
+    /// shifting by the last byte's bad-character entry after each match
+    pub fn overlapping(mut self, overlapping: bool) -> Raita<'n> {
+        self.overlapping = overlapping;
+        self
+    }
+}
+
+impl Scan for Raita<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        let nlen = self.needle.len();
+        let (first, middle, last) = (self.needle[0],
+                                     self.needle[nlen / 2],
+                                     self.needle[nlen - 1]);
+
+        // step up through the haystack
+        let mut outerii = from;
+        while outerii + nlen <= end {
+            let window = &haystack[outerii..outerii + nlen];
+
+            // last, first, middle, then the rest
+            if window[nlen - 1] == last
+               && window[0] == first
+               && window[nlen / 2] == middle
+               && window == self.needle
+            {
+                return Some(outerii);
+            }
+
+            outerii += self.ct[window[nlen - 1] as usize];
+        }
+
+        None
+    }
+
+    fn shift_after_match(&self) -> usize {
+        // shift as if the last byte had mismatched
+        if self.overlapping {
+            self.ct[self.needle[self.needle.len() - 1] as usize]
+        } else {
+            self.needle.len()
+        }
+    }
+}
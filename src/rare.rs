@@ -0,0 +1,134 @@
+use super::matches::Scan;
+use super::simd::find_byte;
+
+// the bytes of English text and source code, most common first,
+// from which the built-in frequencies are ranked;
+// any other byte is rarer than all of these
+const COMMON: &[u8] = b" etaoinsrhldcu\nmfpgwy,b.v\tk_T-SAI\"()CE=M'0;1PRN:BDLF/H\r\
+                        2WOG{}*x<>j3q5z48U69K7V[]JY&!?+Q#%X|Z@$^~\\`";
+
+/// How often each byte occurs in some corpus,
+/// which a `RareByte` searcher consults to pick the rarest bytes of a needle.
+///
+/// The default is a ranking built into the crate
+/// for English text and source code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ByteFrequencies {
+    counts: [u64; 256],
+}
+
+impl ByteFrequencies {
+    /// Frequencies from a count for each byte
+    pub fn new(counts: [u64; 256]) -> ByteFrequencies {
+        ByteFrequencies { counts }
+    }
+
+    /// Frequencies counted from a sample of the haystacks to search
+    pub fn from_corpus(corpus: &[u8]) -> ByteFrequencies {
+        let mut counts = [0; 256];
+        for &byte in corpus {
+            counts[byte as usize] += 1;
+        }
+        ByteFrequencies { counts }
+    }
+
+    /// How often `byte` occurs
+    pub fn frequency(&self, byte: u8) -> u64 {
+        self.counts[byte as usize]
+    }
+
+    // the positions of the rarest byte of `needle`, and the next rarest
+    fn rarest(&self, needle: &[u8]) -> (usize, usize) {
+        let by_frequency = |&ii: &usize| self.frequency(needle[ii]);
+
+        let rare1 = (0..needle.len()).min_by_key(by_frequency).unwrap_or(0);
+        let rare2 = (0..needle.len()).filter(|&ii| ii != rare1)
+            .min_by_key(by_frequency)
+            .unwrap_or(rare1);
+
+        (rare1, rare2)
+    }
+}
+
+impl Default for ByteFrequencies {
+    fn default() -> ByteFrequencies {
+        let mut counts = [0; 256];
+        for (rank, &byte) in COMMON.iter().enumerate() {
+            counts[byte as usize] = (COMMON.len() - rank) as u64;
+        }
+        ByteFrequencies { counts }
+    }
+}
+
+/// A searcher which scans for the rarest byte of its needle,
+/// and checks the window around each occurrence:
+/// first at the next rarest byte, then in full.
+#[derive(Clone, Debug)]
+pub struct RareByte<'n> {
+    needle: &'n [u8],
+    rare1: usize,
+    rare2: usize,
+    overlapping: bool,
+}
+
+impl<'n> RareByte<'n> {
+    /// Pick the rarest bytes of `needle`, using the built-in frequencies
+    pub fn new<N>(needle: &'n N) -> RareByte<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        RareByte::with_frequencies(needle, &ByteFrequencies::default())
+    }
+
+    /// Pick the rarest bytes of `needle`, using `frequencies`
+    pub fn with_frequencies<N>(needle: &'n N,
+                               frequencies: &ByteFrequencies) -> RareByte<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+        let (rare1, rare2) = frequencies.rarest(needle);
+
+        RareByte { needle, rare1, rare2, overlapping: false }
+    }
+
+    /// Also report matches which overlap earlier ones
+    pub fn overlapping(mut self, overlapping: bool) -> RareByte<'n> {
+        self.overlapping = overlapping;
+        self
+    }
+}
+
+impl Scan for RareByte<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        let nlen = self.needle.len();
+
+        // step up through the haystack
+        let mut outerii = from;
+        while outerii + nlen <= end {
+
+            // the next rare byte which lies within a window
+            let found = find_byte(haystack, self.needle[self.rare1],
+                                  outerii + self.rare1,
+                                  end - nlen + self.rare1 + 1)?;
+            let window = found - self.rare1;
+
+            // check the next rarest byte before the rest
+            if haystack[window + self.rare2] == self.needle[self.rare2]
+               && haystack[window..window + nlen] == *self.needle
+            {
+                return Some(window);
+            }
+
+            outerii = window + 1;
+        }
+
+        None
+    }
+
+    fn shift_after_match(&self) -> usize {
+        if self.overlapping { 1 } else { self.needle.len() }
+    }
+}
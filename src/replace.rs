@@ -0,0 +1,138 @@
+use super::BoyerMoore;
+use super::matches::{Matches, Scan};
+
+/// Returns `haystack` with every match of `needle` replaced by `with`
+/// (using Boyer-Moore)
+pub fn replace(haystack: &str, needle: &str, with: &str) -> String {
+    replacen(haystack, needle, with, haystack.len() + 1)
+}
+
+/// Returns `haystack` with the first `nn` matches of `needle`
+/// replaced by `with`
+/// (using Boyer-Moore)
+pub fn replacen(haystack: &str, needle: &str, with: &str,
+                nn: usize) -> String {
+    replace_between(haystack, needle, with, nn, 0, haystack.len())
+}
+
+/// Returns `haystack` with the first `nn` matches of `needle`
+/// between `start` and `end` replaced by `with`
+/// (using Boyer-Moore; as with `str::replace`, an empty needle
+/// matches at every character boundary)
+pub fn replace_between(haystack: &str, needle: &str, with: &str,
+                       nn: usize,
+                       start: usize, end: usize) -> String {
+    assert!(start <= end);
+    assert!(end <= haystack.len());
+
+    let replaced = if needle.is_empty() {
+        let boundaries = haystack[start..end].char_indices()
+            .map(|(ii, _)| start + ii)
+            .chain([end])
+            .take(nn)
+            .map(|ii| (ii, ii))
+            .collect();
+        splice(haystack.as_bytes(), with.as_bytes(), boundaries)
+    } else {
+        replaced(&BoyerMoore::new(needle), haystack.as_bytes(),
+                 with.as_bytes(), nn, start, end)
+    };
+
+    // a str needle only matches whole characters of a str haystack
+    String::from_utf8(replaced).expect("replaced whole characters")
+}
+
+// the spans to replace, up to `nn` of them,
+// merging each run of overlapping matches into one
+// (an empty needle matches at every byte)
+fn spans(searcher: &dyn Scan, haystack: &[u8],
+         nn: usize,
+         start: usize, end: usize) -> Vec<(usize, usize)> {
+    let nlen = searcher.needle_len();
+
+    if nlen == 0 {
+        return (start..=end).take(nn).map(|ii| (ii, ii)).collect();
+    }
+
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+
+    for found in Matches::new(searcher, haystack, start, end) {
+        match spans.last_mut() {
+            Some(last) if found < last.1 => last.1 = found + nlen,
+            _ => {
+                if spans.len() >= nn { break; }
+                spans.push((found, found + nlen));
+            }
+        }
+    }
+
+    spans
+}
+
+// copy `haystack` with the matches replaced
+pub(crate) fn replaced(searcher: &dyn Scan, haystack: &[u8], with: &[u8],
+                       nn: usize,
+                       start: usize, end: usize) -> Vec<u8> {
+    splice(haystack, with, spans(searcher, haystack, nn, start, end))
+}
+
+// copy `haystack` with the spans replaced, in a single pass
+fn splice(haystack: &[u8], with: &[u8], spans: Vec<(usize, usize)>) -> Vec<u8> {
+    let mut results = Vec::with_capacity(haystack.len());
+
+    let mut copied = 0;
+    for (from, to) in spans {
+        results.extend_from_slice(&haystack[copied..from]);
+        results.extend_from_slice(with);
+        copied = to;
+    }
+    results.extend_from_slice(&haystack[copied..]);
+
+    results
+}
+
+// replace the spans within `buffer`, returning how many were replaced
+pub(crate) fn replace_in_place(searcher: &dyn Scan, buffer: &mut Vec<u8>,
+                               with: &[u8]) -> usize {
+    let len = buffer.len();
+    let spans = spans(searcher, buffer, len + 1, 0, len);
+    let wlen = with.len();
+
+    if spans.iter().all(|&(from, to)| wlen <= to - from) {
+        // shrinking: move everything down, front to back
+        let mut read = 0;
+        let mut write = 0;
+        for &(from, to) in &spans {
+            buffer.copy_within(read..from, write);
+            write += from - read;
+            buffer[write..write + wlen].copy_from_slice(with);
+            write += wlen;
+            read = to;
+        }
+        buffer.copy_within(read..len, write);
+        buffer.truncate(write + len - read);
+
+    } else if spans.iter().all(|&(from, to)| to - from <= wlen) {
+        // growing: move everything up, back to front
+        let grown: usize = spans.iter()
+            .map(|&(from, to)| wlen - (to - from))
+            .sum();
+        buffer.resize(len + grown, 0);
+
+        let mut read = len;
+        let mut write = len + grown;
+        for &(from, to) in spans.iter().rev() {
+            write -= read - to;
+            buffer.copy_within(to..read, write);
+            write -= wlen;
+            buffer[write..write + wlen].copy_from_slice(with);
+            read = from;
+        }
+
+    } else {
+        // merged spans both shorter and longer than `with`
+        *buffer = replaced(searcher, buffer, with, len + 1, 0, len);
+    }
+
+    spans.len()
+}
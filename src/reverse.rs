@@ -0,0 +1,244 @@
+use super::{boyer_moore_matching_suffixes, boyer_moore_shift,
+            boyer_moore_unmatched_chars, horspool_shift};
+use super::error::{check_range, SearchError};
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`, last match first
+/// (using Boyer-Moore, scanning from `end` back to `start`)
+pub fn boyer_moore_reverse_search(haystack: &str, needle: &str,
+                                  nn: usize,
+                                  start: usize, end: usize) -> Vec<usize> {
+    ReverseBoyerMoore::new(needle).rfindn_between(haystack, nn, start, end)
+}
+
+/// the same, but Boyer-Moore-Horspool
+pub fn boyer_moore_horspool_reverse_search(haystack: &str, needle: &str,
+                                           nn: usize,
+                                           start: usize, end: usize)
+                                           -> Vec<usize> {
+    ReverseHorspool::new(needle).rfindn_between(haystack, nn, start, end)
+}
+
+/// A Boyer-Moore searcher which scans right to left,
+/// with its tables mirrored to key on prefixes of the needle
+#[derive(Clone, Debug)]
+pub struct ReverseBoyerMoore<'n> {
+    needle: &'n [u8],
+    ct: Vec<usize>,
+    pt: Vec<usize>,
+}
+
+impl<'n> ReverseBoyerMoore<'n> {
+    /// Compute the mirrored Boyer-Moore tables for `needle`,
+    /// which may be a `str` or any other bytes
+    pub fn new<N>(needle: &'n N) -> ReverseBoyerMoore<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+
+        // no tables for an empty needle, which matches immediately
+        if needle.is_empty() {
+            return ReverseBoyerMoore { needle, ct: Vec::new(), pt: Vec::new() };
+        }
+
+        // the prefixes of the needle are the suffixes of its reversal
+        let reversed: Vec<u8> = needle.iter().rev().copied().collect();
+
+        ReverseBoyerMoore {
+            needle,
+            ct: boyer_moore_unmatched_chars(&reversed),
+            pt: boyer_moore_matching_suffixes(&reversed),
+        }
+    }
+}
+
+impl RScan for ReverseBoyerMoore<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn rscan(&self, haystack: &[u8], start: usize, end: usize) -> Option<usize> {
+        rscan(self.needle, haystack, start, end,
+              |pos, ch| boyer_moore_shift(&self.ct, &self.pt,
+                                          self.needle.len(), pos, ch))
+    }
+}
+
+/// A Boyer-Moore-Horspool searcher which scans right to left,
+/// with its table mirrored to key on prefixes of the needle
+#[derive(Clone, Debug)]
+pub struct ReverseHorspool<'n> {
+    needle: &'n [u8],
+    ct: Vec<usize>,
+}
+
+impl<'n> ReverseHorspool<'n> {
+    /// Compute the mirrored Boyer-Moore-Horspool table for `needle`,
+    /// which may be a `str` or any other bytes
+    pub fn new<N>(needle: &'n N) -> ReverseHorspool<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+
+        // no table for an empty needle, which matches immediately
+        if needle.is_empty() {
+            return ReverseHorspool { needle, ct: Vec::new() };
+        }
+
+        // the prefixes of the needle are the suffixes of its reversal
+        let reversed: Vec<u8> = needle.iter().rev().copied().collect();
+
+        ReverseHorspool { needle, ct: boyer_moore_unmatched_chars(&reversed) }
+    }
+}
+
+impl RScan for ReverseHorspool<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn rscan(&self, haystack: &[u8], start: usize, end: usize) -> Option<usize> {
+        rscan(self.needle, haystack, start, end,
+              |pos, ch| horspool_shift(&self.ct, self.needle.len(), pos, ch))
+    }
+}
+
+// the scanning step of a reverse searcher
+// (public only so `ReverseSearcher` can build on it, but not nameable outside)
+pub trait RScan {
+    fn needle_len(&self) -> usize;
+
+    // returns the last match starting at or after `start`
+    // and ending by `end`
+    fn rscan(&self, haystack: &[u8], start: usize, end: usize) -> Option<usize>;
+}
+
+/// The methods shared by the reverse searchers,
+/// all built on their scanning step.
+///
+/// Bring it into scope (`use search::ReverseSearcher;`) to call them.
+pub trait ReverseSearcher: RScan + Sized {
+    /// Returns the byte position of the last match, if any
+    fn rfind<H>(&self, haystack: &H) -> Option<usize>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        self.rfind_between(haystack, 0, haystack.len())
+    }
+
+    /// Returns the byte position of the last match
+    /// between `start` and `end`, if any
+    fn rfind_between<H>(&self, haystack: &H,
+                        start: usize, end: usize) -> Option<usize>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+
+        assert!(start <= end);
+        assert!(end <= haystack.len());
+
+        self.rscan(haystack, start, end)
+    }
+
+    /// Returns up to `nn` byte positions of matches, last match first
+    fn rfind_n<H>(&self, haystack: &H, nn: usize) -> Vec<usize>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        self.rfindn_between(haystack, nn, 0, haystack.len())
+    }
+
+    /// Returns up to `nn` byte positions of matches
+    /// between `start` and `end`, last match first
+    fn rfindn_between<H>(&self, haystack: &H, nn: usize,
+                         start: usize, end: usize) -> Vec<usize>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+
+        assert!(start <= end);
+        assert!(end <= haystack.len());
+
+        // no matches asked for
+        if nn == 0 {
+            return Vec::new();
+        }
+
+        // empty needle
+        if self.needle_len() == 0 {
+            return vec![end];
+        }
+
+        let mut results = Vec::new();
+
+        // rescan before each match found
+        let mut end = end;
+        while let Some(found) = self.rscan(haystack, start, end) {
+            results.push(found);
+
+            if results.len() >= nn { return results; }
+
+            end = found;
+        }
+
+        results
+    }
+
+    /// the same, but returning an error for a bad range
+    fn try_rfindn_between<H>(&self, haystack: &H, nn: usize,
+                             start: usize, end: usize)
+                             -> Result<Vec<usize>, SearchError>
+        where H: AsRef<[u8]> + ?Sized
+    {
+        let haystack = haystack.as_ref();
+        check_range(haystack.len(), start, end)?;
+        Ok(self.rfindn_between(haystack, nn, start, end))
+    }
+}
+
+impl<S: RScan> ReverseSearcher for S {}
+
+// the forward scan, mirrored:
+// windows step down from `end`, and each is compared left to right,
+// so `shift` is given the position within the reversed needle
+fn rscan(needle: &[u8], haystack: &[u8],
+         start: usize, end: usize,
+         shift: impl Fn(usize, u8) -> usize) -> Option<usize> {
+    let nlen = needle.len();
+
+    // empty needle
+    if nlen == 0 {
+        return Some(end);
+    }
+
+    // step down through the haystack,
+    // with outerii just after the window
+    let mut outerii = end;
+    while start + nlen <= outerii {
+        let window = outerii - nlen;
+
+        // step forward through needle
+        let mut windowii = 0;
+        while windowii < nlen {
+
+            // matching byte?
+            if needle[windowii] == haystack[window + windowii] {
+
+                // needle fully matched?
+                if windowii == nlen - 1 {
+                    return Some(window);
+                }
+
+                windowii += 1;
+
+            } else {
+                // no match or a partial match
+                outerii -= shift(nlen - 1 - windowii,
+                                 haystack[window + windowii]);
+                break;
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,122 @@
+// a "generic SIMD" prefilter:
+// compare the first and last bytes of the needle against a block
+// of 16 or 32 windows at once, and only check whole windows
+// where both of those match
+
+// scan whole blocks of windows starting from `*from`,
+// returning the first match, or else advancing `*from`
+// to the first window left for the scalar loop
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn prefilter(haystack: &[u8], needle: &[u8],
+                        from: &mut usize, end: usize) -> Option<usize> {
+    if is_x86_feature_detected!("avx2") {
+        // safety: AVX2 is available
+        unsafe { x86::avx2(haystack, needle, from, end) }
+    } else {
+        // safety: SSE2 is always available on x86_64
+        unsafe { x86::sse2(haystack, needle, from, end) }
+    }
+}
+
+// no vector instructions, so leave it all to the scalar loop
+#[cfg(not(target_arch = "x86_64"))]
+pub(crate) fn prefilter(_haystack: &[u8], _needle: &[u8],
+                        _from: &mut usize, _end: usize) -> Option<usize> {
+    None
+}
+
+// the first `byte` between `from` and `end`,
+// filtering whole blocks first then scanning the rest
+pub(crate) fn find_byte(haystack: &[u8], byte: u8,
+                        from: usize, end: usize) -> Option<usize> {
+    let mut from = from;
+    if let Some(found) = prefilter(haystack, &[byte], &mut from, end) {
+        return Some(found);
+    }
+
+    haystack[from..end].iter()
+        .position(|&ch| ch == byte)
+        .map(|ii| from + ii)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    // the blocks loaded for a window start at `*from` and `*from + nlen - 1`,
+    // and the loop only runs while both end within `end`,
+    // which is within the haystack
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn sse2(haystack: &[u8], needle: &[u8],
+                              from: &mut usize, end: usize) -> Option<usize> {
+        const BLOCK: usize = 16;
+
+        let nlen = needle.len();
+        let first = _mm_set1_epi8(needle[0] as i8);
+        let last = _mm_set1_epi8(needle[nlen - 1] as i8);
+
+        while *from + nlen - 1 + BLOCK <= end {
+            let ptr = haystack.as_ptr().add(*from);
+            let firsts = _mm_loadu_si128(ptr as *const __m128i);
+            let lasts = _mm_loadu_si128(ptr.add(nlen - 1) as *const __m128i);
+
+            let both = _mm_and_si128(_mm_cmpeq_epi8(first, firsts),
+                                     _mm_cmpeq_epi8(last, lasts));
+            let mask = _mm_movemask_epi8(both) as u32;
+
+            if let Some(found) = verify(haystack, needle, *from, mask) {
+                return Some(found);
+            }
+
+            *from += BLOCK;
+        }
+
+        None
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn avx2(haystack: &[u8], needle: &[u8],
+                              from: &mut usize, end: usize) -> Option<usize> {
+        const BLOCK: usize = 32;
+
+        let nlen = needle.len();
+        let first = _mm256_set1_epi8(needle[0] as i8);
+        let last = _mm256_set1_epi8(needle[nlen - 1] as i8);
+
+        while *from + nlen - 1 + BLOCK <= end {
+            let ptr = haystack.as_ptr().add(*from);
+            let firsts = _mm256_loadu_si256(ptr as *const __m256i);
+            let lasts = _mm256_loadu_si256(ptr.add(nlen - 1) as *const __m256i);
+
+            let both = _mm256_and_si256(_mm256_cmpeq_epi8(first, firsts),
+                                        _mm256_cmpeq_epi8(last, lasts));
+            let mask = _mm256_movemask_epi8(both) as u32;
+
+            if let Some(found) = verify(haystack, needle, *from, mask) {
+                return Some(found);
+            }
+
+            *from += BLOCK;
+        }
+
+        None
+    }
+
+    // check each candidate window in the block, in order
+    fn verify(haystack: &[u8], needle: &[u8],
+              block: usize, mut mask: u32) -> Option<usize> {
+        while mask != 0 {
+            let candidate = block + mask.trailing_zeros() as usize;
+
+            if &haystack[candidate..candidate + needle.len()] == needle {
+                return Some(candidate);
+            }
+
+            // clear the lowest candidate
+            mask &= mask - 1;
+        }
+
+        None
+    }
+}
@@ -0,0 +1,114 @@
+use std::iter::FusedIterator;
+
+use super::matches::{Matches, Scan};
+use super::reverse::{ReverseHorspool, ReverseSearcher};
+
+/// An iterator over the pieces of a haystack between matches,
+/// searching lazily for each separator in turn.
+///
+/// Matches overlapping an earlier separator are skipped.
+pub struct Split<'h, 'n> {
+    matches: Matches<'h, 'n>,
+    haystack: &'h [u8],
+    nlen: usize,
+    position: usize,
+    remaining: usize, // pieces left to return
+    inclusive: bool,
+}
+
+impl<'h, 'n> Split<'h, 'n> {
+    pub(crate) fn new(searcher: &'n dyn Scan, haystack: &'h [u8],
+                      remaining: usize, inclusive: bool) -> Split<'h, 'n> {
+        let nlen = searcher.needle_len();
+        assert!(0 < nlen);
+
+        Split {
+            matches: Matches::new(searcher, haystack, 0, haystack.len()),
+            haystack,
+            nlen,
+            position: 0,
+            remaining,
+            inclusive,
+        }
+    }
+}
+
+impl<'h> Iterator for Split<'h, '_> {
+    type Item = &'h [u8];
+
+    fn next(&mut self) -> Option<&'h [u8]> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        // up to the next separator, unless this is the last piece allowed
+        if self.remaining > 0 {
+            let position = self.position;
+            if let Some(found) = self.matches.find(|&found| found >= position) {
+                let piece_end = if self.inclusive { found + self.nlen } else { found };
+                self.position = found + self.nlen;
+                return Some(&self.haystack[position..piece_end]);
+            }
+        }
+
+        // the rest, which is only empty after an inclusive piece
+        // if it follows a separator
+        self.remaining = 0;
+        if self.inclusive && self.position == self.haystack.len() {
+            return None;
+        }
+        Some(&self.haystack[self.position..])
+    }
+}
+
+impl FusedIterator for Split<'_, '_> {}
+
+/// An iterator over the pieces of a haystack between matches,
+/// last piece first, searching lazily from the end
+pub struct RSplit<'h, 'n> {
+    searcher: &'n ReverseHorspool<'n>,
+    haystack: &'h [u8],
+    nlen: usize,
+    position: usize,
+    done: bool,
+}
+
+impl<'h, 'n> RSplit<'h, 'n> {
+    pub(crate) fn new(searcher: &'n ReverseHorspool<'n>, nlen: usize,
+                      haystack: &'h [u8]) -> RSplit<'h, 'n> {
+        assert!(0 < nlen);
+
+        RSplit {
+            searcher,
+            haystack,
+            nlen,
+            position: haystack.len(),
+            done: false,
+        }
+    }
+}
+
+impl<'h> Iterator for RSplit<'h, '_> {
+    type Item = &'h [u8];
+
+    fn next(&mut self) -> Option<&'h [u8]> {
+        if self.done {
+            return None;
+        }
+
+        let position = self.position;
+        match self.searcher.rfind_between(self.haystack, 0, position) {
+            Some(found) => {
+                self.position = found;
+                Some(&self.haystack[found + self.nlen..position])
+            }
+            None => {
+                self.done = true;
+                Some(&self.haystack[..position])
+            }
+        }
+    }
+}
+
+impl FusedIterator for RSplit<'_, '_> {}
@@ -0,0 +1,130 @@
+use std::io::{self, Read};
+
+use super::matches::Scan;
+
+// how much to read from a reader at once
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A search over a stream of bytes, fed in successive chunks.
+///
+/// Each chunk is scanned where it lies; only the tail of the stream
+/// which could begin a match (less than a needle's length) is copied,
+/// and carried over to be joined with the start of the next chunk.
+/// Matches are reported as absolute byte offsets within the whole stream.
+pub struct StreamSearch<'s> {
+    searcher: &'s dyn Scan,
+    carry: Vec<u8>,
+    offset: usize, // absolute offset of carry[0]
+    resume: usize, // where a match may next begin, relative to carry[0]
+    started: bool,
+}
+
+impl<'s> StreamSearch<'s> {
+    pub(crate) fn new(searcher: &'s dyn Scan) -> StreamSearch<'s> {
+        StreamSearch {
+            searcher,
+            carry: Vec::new(),
+            offset: 0,
+            resume: 0,
+            started: false,
+        }
+    }
+
+    /// Search the next chunk of the stream,
+    /// returning the offsets of matches which end within it
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<usize> {
+        let mut results = Vec::new();
+
+        let nlen = self.searcher.needle_len();
+
+        // empty needle, matching once at the start
+        if nlen == 0 {
+            if !self.started {
+                results.push(0);
+            }
+            self.started = true;
+            return results;
+        }
+        self.started = true;
+
+        let shift = self.searcher.shift_after_match();
+
+        // the first position where a match may still begin,
+        // and where the chunk begins
+        let mut next = self.offset + self.resume;
+        let chunk_start = self.offset + self.carry.len();
+
+        // matches beginning in the carried tail,
+        // completed by (at most) the first `nlen - 1` bytes of the chunk
+        if next < chunk_start {
+            let carried = self.carry.len();
+            self.carry.extend_from_slice(&chunk[..chunk.len().min(nlen - 1)]);
+
+            while next < chunk_start {
+                match self.searcher.scan(&self.carry, next - self.offset,
+                                         self.carry.len()) {
+                    Some(found) if found < carried => {
+                        results.push(self.offset + found);
+                        next = self.offset + found + shift;
+                    }
+                    _ => break,
+                }
+            }
+
+            self.carry.truncate(carried);
+        }
+
+        // matches beginning in the chunk, scanned where it lies
+        let mut from = next.saturating_sub(chunk_start);
+        while from < chunk.len() {
+            match self.searcher.scan(chunk, from, chunk.len()) {
+                Some(found) => {
+                    results.push(chunk_start + found);
+                    from = found + shift;
+                    next = chunk_start + from;
+                }
+                None => break,
+            }
+        }
+
+        // keep the bytes which may begin a match completed by the next chunk
+        let end = chunk_start + chunk.len();
+        let keep = next.max(end.saturating_sub(nlen - 1)).min(end);
+
+        if keep >= chunk_start {
+            self.carry.clear();
+            self.carry.extend_from_slice(&chunk[keep - chunk_start..]);
+        } else {
+            // a chunk shorter than the tail
+            self.carry.drain(..keep - self.offset);
+            self.carry.extend_from_slice(chunk);
+        }
+        self.offset = keep;
+        self.resume = next.saturating_sub(keep);
+
+        results
+    }
+
+    /// Search everything remaining in `reader`,
+    /// returning the offsets of all matches
+    pub fn read_from<R: Read>(&mut self, mut reader: R) -> io::Result<Vec<usize>> {
+        let mut results = Vec::new();
+        let mut chunk = vec![0; CHUNK_SIZE];
+
+        loop {
+            let read = match reader.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+
+            results.extend(self.feed(&chunk[..read]));
+        }
+
+        // an empty stream still matches an empty needle
+        results.extend(self.feed(&[]));
+
+        Ok(results)
+    }
+}
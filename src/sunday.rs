@@ -0,0 +1,106 @@
+use super::boyer_moore_unmatched_chars;
+use super::matches::{Scan, Searcher};
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`
+/// (using Sunday's Quick Search)
+pub fn sunday_search(haystack: &str, needle: &str,
+                     nn: usize,
+                     start: usize, end: usize) -> Vec<usize> {
+    sunday_search_bytes(haystack, needle, nn, start, end)
+}
+
+/// the same, but over any bytes
+pub fn sunday_search_bytes<H, N>(haystack: &H, needle: &N,
+                                 nn: usize,
+                                 start: usize, end: usize) -> Vec<usize>
+    where H: AsRef<[u8]> + ?Sized, N: AsRef<[u8]> + ?Sized
+{
+    Sunday::new(needle).findn_between(haystack, nn, start, end)
+}
+
+/// the same, but also reporting matches which overlap earlier ones
+pub fn sunday_search_overlapping(haystack: &str, needle: &str,
+                                 nn: usize,
+                                 start: usize, end: usize) -> Vec<usize> {
+    Sunday::new(needle).overlapping(true)
+        .findn_between(haystack, nn, start, end)
+}
+
+/// A Quick Search searcher, after Sunday.
+///
+/// Each window is compared in full, then shifted on the byte just after it,
+/// which must line up with an occurrence of that byte in the needle,
+/// so the shift may be a byte longer than the needle.
+#[derive(Clone, Debug)]
+pub struct Sunday<'n> {
+    needle: &'n [u8],
+    qs: Vec<usize>,
+    period: usize,
+    overlapping: bool,
+}
+
+impl<'n> Sunday<'n> {
+    /// Compute the Quick Search table for `needle`,
+    /// which may be a `str` or any other bytes
+    pub fn new<N>(needle: &'n N) -> Sunday<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+        let len = needle.len();
+
+        // no table for an empty needle, which matches immediately
+        if len == 0 {
+            return Sunday { needle, qs: Vec::new(), period: 0, overlapping: false };
+        }
+
+        // the bad-character table, counting from one byte further on,
+        // where the last byte of the needle now counts too
+        let ct = boyer_moore_unmatched_chars(needle);
+        let period = ct[needle[len - 1] as usize];
+
+        let mut qs: Vec<usize> = ct.iter().map(|delta| delta + 1).collect();
+        qs[needle[len - 1] as usize] = 1;
+
+        Sunday { needle, qs, period, overlapping: false }
+    }
+
+    /// Also report matches which overlap earlier ones,
+    /// shifting to the last byte's previous occurrence after each match
+    pub fn overlapping(mut self, overlapping: bool) -> Sunday<'n> {
+        self.overlapping = overlapping;
+        self
+    }
+}
+
+impl Scan for Sunday<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        let nlen = self.needle.len();
+
+        // step up through the haystack
+        let mut outerii = from;
+        while outerii + nlen <= end {
+
+            // compare the whole window
+            if haystack[outerii..outerii + nlen] == *self.needle {
+                return Some(outerii);
+            }
+
+            // the byte after the window, if it's within range
+            if outerii + nlen == end {
+                break;
+            }
+            outerii += self.qs[haystack[outerii + nlen] as usize];
+        }
+
+        None
+    }
+
+    fn shift_after_match(&self) -> usize {
+        if self.overlapping { self.period } else { self.needle.len() }
+    }
+}
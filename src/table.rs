@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::error::SearchError;
+
+/// A symbol of an alphabet which can key a bad-character table
+pub trait Symbol: Copy + Eq + Hash {
+    /// The number of symbols in the alphabet,
+    /// if it is small enough for a dense table
+    const ALPHABET_SIZE: Option<usize>;
+
+    /// The position of this symbol within a dense table
+    fn index(self) -> usize;
+}
+
+impl Symbol for u8 {
+    const ALPHABET_SIZE: Option<usize> = Some(1 << 8);
+
+    fn index(self) -> usize { self as usize }
+}
+
+// a dense table of u16s would take 65536 shifts for every needle,
+// so only the needle's symbols are kept
+impl Symbol for u16 {
+    const ALPHABET_SIZE: Option<usize> = None;
+
+    fn index(self) -> usize { self as usize }
+}
+
+impl Symbol for u32 {
+    const ALPHABET_SIZE: Option<usize> = None;
+
+    fn index(self) -> usize { self as usize }
+}
+
+impl Symbol for char {
+    const ALPHABET_SIZE: Option<usize> = None;
+
+    fn index(self) -> usize { self as usize }
+}
+
+/// A bad-character table over any alphabet:
+/// an array covering every symbol for small alphabets,
+/// and a map of the needle's symbols for large ones
+#[derive(Clone, Debug)]
+pub struct UnmatchedTable<T: Symbol> {
+    deltas: Deltas<T>,
+}
+
+// which of the two layouts a table uses
+#[derive(Clone, Debug)]
+enum Deltas<T> {
+    Dense(Vec<usize>),
+    Sparse { deltas: HashMap<T, usize>, default: usize },
+}
+
+impl<T: Symbol> UnmatchedTable<T> {
+    /// Compute the table used to choose a shift based on
+    /// an unmatched symbol's possible position within `needle`
+    pub fn new(needle: &[T]) -> UnmatchedTable<T> {
+        let len = needle.len();
+
+        assert!(0 < len);
+
+        let mut table = match T::ALPHABET_SIZE {
+            Some(size) => Deltas::Dense(vec![len; size]),
+            None => Deltas::Sparse {
+                deltas: HashMap::new(),
+                default: len,
+            },
+        };
+
+        // from last-1 to first, keeping the first shift set for each symbol
+        for (jj, &symbol) in needle[..len - 1].iter().enumerate().rev() {
+            let delta = len - 1 - jj;
+
+            match table {
+                Deltas::Dense(ref mut deltas) => {
+                    if deltas[symbol.index()] == len {
+                        deltas[symbol.index()] = delta;
+                    }
+                }
+                Deltas::Sparse { ref mut deltas, .. } => {
+                    deltas.entry(symbol).or_insert(delta);
+                }
+            }
+        }
+
+        UnmatchedTable { deltas: table }
+    }
+
+    /// the same, but returning an error for an empty needle
+    pub fn try_new(needle: &[T]) -> Result<UnmatchedTable<T>, SearchError> {
+        if needle.is_empty() {
+            return Err(SearchError::EmptyNeedle);
+        }
+        Ok(UnmatchedTable::new(needle))
+    }
+
+    /// The shift for an unmatched `symbol`
+    pub fn get(&self, symbol: T) -> usize {
+        match self.deltas {
+            Deltas::Dense(ref deltas) => deltas[symbol.index()],
+            Deltas::Sparse { ref deltas, default } =>
+                deltas.get(&symbol).copied().unwrap_or(default),
+        }
+    }
+}
+
+/// A bad-character table for a needle of symbols of type `T`
+pub trait ShiftTable<T> {
+    /// The shift for an unmatched `symbol`
+    fn shift(&self, symbol: &T) -> usize;
+}
+
+impl<T: Symbol> ShiftTable<T> for UnmatchedTable<T> {
+    fn shift(&self, symbol: &T) -> usize {
+        self.get(*symbol)
+    }
+}
+
+/// A bad-character table for any alphabet of hashable symbols,
+/// keyed by the symbols of the needle it borrows
+#[derive(Clone, Debug)]
+pub struct HashedTable<'n, T> {
+    deltas: HashMap<&'n T, usize>,
+    default: usize,
+}
+
+impl<'n, T: Eq + Hash> HashedTable<'n, T> {
+    /// Compute the table used to choose a shift based on
+    /// an unmatched symbol's possible position within `needle`
+    pub fn new(needle: &'n [T]) -> HashedTable<'n, T> {
+        let len = needle.len();
+
+        assert!(0 < len);
+
+        // from last-1 to first, keeping the first shift set for each symbol
+        let mut deltas = HashMap::new();
+        for (jj, symbol) in needle[..len - 1].iter().enumerate().rev() {
+            deltas.entry(symbol).or_insert(len - 1 - jj);
+        }
+
+        HashedTable { deltas, default: len }
+    }
+
+    /// the same, but returning an error for an empty needle
+    pub fn try_new(needle: &'n [T]) -> Result<HashedTable<'n, T>, SearchError> {
+        if needle.is_empty() {
+            return Err(SearchError::EmptyNeedle);
+        }
+        Ok(HashedTable::new(needle))
+    }
+}
+
+impl<T: Eq + Hash> ShiftTable<T> for HashedTable<'_, T> {
+    fn shift(&self, symbol: &T) -> usize {
+        self.deltas.get(symbol).copied().unwrap_or(self.default)
+    }
+}
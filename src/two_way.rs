@@ -0,0 +1,179 @@
+use super::matches::{Resume, Scan, Searcher};
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`
+/// (using Two-Way, in constant space and linear time)
+pub fn two_way_search(haystack: &str, needle: &str,
+                      nn: usize,
+                      start: usize, end: usize) -> Vec<usize> {
+    two_way_search_bytes(haystack, needle, nn, start, end)
+}
+
+/// the same, but over any bytes
+pub fn two_way_search_bytes<H, N>(haystack: &H, needle: &N,
+                                  nn: usize,
+                                  start: usize, end: usize) -> Vec<usize>
+    where H: AsRef<[u8]> + ?Sized, N: AsRef<[u8]> + ?Sized
+{
+    TwoWay::new(needle).findn_between(haystack, nn, start, end)
+}
+
+/// the same, but also reporting matches which overlap earlier ones
+pub fn two_way_search_overlapping(haystack: &str, needle: &str,
+                                  nn: usize,
+                                  start: usize, end: usize) -> Vec<usize> {
+    TwoWay::new(needle).overlapping(true)
+        .findn_between(haystack, nn, start, end)
+}
+
+/// A Two-Way searcher, after Crochemore and Perrin.
+///
+/// The needle is split at a critical factorization into a left and right
+/// part: each window compares the right part forwards, then the left part
+/// backwards, and shifts by how far the right part matched or by the
+/// needle's period. Needles which repeat with that period remember
+/// how much of the previous window matched, so no byte of the haystack
+/// is compared more than twice, and nothing is kept but a few positions.
+#[derive(Clone, Debug)]
+pub struct TwoWay<'n> {
+    needle: &'n [u8],
+    crit_pos: usize,
+    period: usize,
+    periodic: bool,
+    overlapping: bool,
+}
+
+impl<'n> TwoWay<'n> {
+    /// Compute the critical factorization of `needle`,
+    /// which may be a `str` or any other bytes
+    pub fn new<N>(needle: &'n N) -> TwoWay<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+        let len = needle.len();
+
+        // the later of the maximal suffixes for either byte order
+        // starts a critical factorization
+        let (pos_less, period_less) = maximal_suffix(needle, false);
+        let (pos_greater, period_greater) = maximal_suffix(needle, true);
+        let (crit_pos, period) = if pos_less > pos_greater {
+            (pos_less, period_less)
+        } else {
+            (pos_greater, period_greater)
+        };
+
+        // does the left part repeat within the right part's period?
+        if crit_pos + period <= len
+           && needle[..crit_pos] == needle[period..period + crit_pos]
+        {
+            TwoWay { needle, crit_pos, period, periodic: true, overlapping: false }
+        } else {
+            // otherwise, a lower bound on the period
+            let period = crit_pos.max(len - crit_pos) + 1;
+            TwoWay { needle, crit_pos, period, periodic: false, overlapping: false }
+        }
+    }
+
+    /// Also report matches which overlap earlier ones,
+    /// shifting by the needle's period (or a lower bound) after each match
+    pub fn overlapping(mut self, overlapping: bool) -> TwoWay<'n> {
+        self.overlapping = overlapping;
+        self
+    }
+}
+
+impl Scan for TwoWay<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        self.resume(haystack, from, end, &mut Resume::default())
+    }
+
+    fn shift_after_match(&self) -> usize {
+        if self.overlapping { self.period } else { self.needle.len() }
+    }
+
+    fn resume(&self, haystack: &[u8], from: usize, end: usize,
+              state: &mut Resume) -> Option<usize> {
+        let nlen = self.needle.len();
+
+        // how much of the needle's start is known to match
+        // (only for periodic needles), carried over from the last match
+        let mut memory = if self.periodic { state.known } else { 0 };
+
+        // step up through the haystack
+        let mut outerii = from;
+        while outerii + nlen <= end {
+
+            // step forward through the right part
+            let mut ii = self.crit_pos.max(memory);
+            while ii < nlen && self.needle[ii] == haystack[outerii + ii] {
+                ii += 1;
+            }
+            if ii < nlen {
+                // shift past the matched part
+                outerii += ii - self.crit_pos + 1;
+                memory = 0;
+                continue;
+            }
+
+            // step back through the left part, down to what's remembered
+            let mut jj = self.crit_pos;
+            while jj > memory && self.needle[jj - 1] == haystack[outerii + jj - 1] {
+                jj -= 1;
+            }
+            if jj > memory {
+                // shift by the period, remembering the overlap if it repeats
+                outerii += self.period;
+                if self.periodic {
+                    memory = nlen - self.period;
+                }
+                continue;
+            }
+
+            // shifting by the period keeps the rest of the match
+            state.known = nlen - self.shift_after_match();
+            return Some(outerii);
+        }
+
+        None
+    }
+}
+
+// the start of the maximal suffix of `needle`,
+// ordering bytes backwards if `reversed`, and that suffix's period
+fn maximal_suffix(needle: &[u8], reversed: bool) -> (usize, usize) {
+    let mut left = 0;   // start of the best suffix so far
+    let mut right = 1;  // start of the suffix compared with it
+    let mut offset = 0; // how far they have matched
+    let mut period = 1;
+
+    while let Some(&aa) = needle.get(right + offset) {
+        let bb = needle[left + offset];
+
+        if (aa < bb && !reversed) || (aa > bb && reversed) {
+            // the suffix at right is smaller, so skip past it
+            right += offset + 1;
+            offset = 0;
+            period = right - left;
+        } else if aa == bb {
+            // advance, and step on by a period at the end of one
+            if offset + 1 == period {
+                right += offset + 1;
+                offset = 0;
+            } else {
+                offset += 1;
+            }
+        } else {
+            // the suffix at right is larger, so start again from it
+            left = right;
+            right += 1;
+            offset = 0;
+            period = 1;
+        }
+    }
+
+    (left, period)
+}
@@ -0,0 +1,126 @@
+use super::adaptive::findn_str_between;
+use super::error::{check_range, SearchError};
+use super::matches::{Collect, Finder};
+
+/// A searcher for `str` haystacks which respects char boundaries.
+///
+/// A `start` or `end` within a multibyte character either panics,
+/// or with `snap` set is moved inwards to the nearest boundary
+/// (finding nothing if there is none between them),
+/// so every match reported lies on char boundaries.
+/// Matches may be reported as byte offsets, char indices
+/// or line and column positions.
+#[derive(Clone, Copy, Debug)]
+pub struct StrSearcher<'n> {
+    needle: &'n str,
+    snap: bool,
+}
+
+/// Where a match begins within a `str`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    /// the byte offset
+    pub byte: usize,
+    /// the char index
+    pub char: usize,
+    /// the line, counting from 1
+    pub line: usize,
+    /// the column in chars, counting from 1
+    pub column: usize,
+}
+
+impl<'n> StrSearcher<'n> {
+    /// Prepare to search for `needle`
+    pub fn new(needle: &'n str) -> StrSearcher<'n> {
+        StrSearcher { needle, snap: false }
+    }
+
+    /// Move a `start` or `end` within a multibyte character
+    /// inwards to a char boundary, rather than panicking
+    pub fn snap(mut self, snap: bool) -> StrSearcher<'n> {
+        self.snap = snap;
+        self
+    }
+
+    /// Returns up to `nn` char indices of matches
+    /// between the byte offsets `start` and `end`
+    pub fn findn_chars_between(&self, haystack: &str, nn: usize,
+                               start: usize, end: usize) -> Vec<usize> {
+        self.positions_between(haystack, nn, start, end).iter()
+            .map(|pos| pos.char)
+            .collect()
+    }
+
+    /// Returns the positions of all matches
+    pub fn positions(&self, haystack: &str) -> Vec<Position> {
+        self.positions_between(haystack, usize::MAX, 0, haystack.len())
+    }
+
+    /// Returns up to `nn` positions of matches
+    /// between the byte offsets `start` and `end`
+    pub fn positions_between(&self, haystack: &str, nn: usize,
+                             start: usize, end: usize) -> Vec<Position> {
+        let mut pos = Position { byte: 0, char: 0, line: 1, column: 1 };
+
+        // walk forwards from one match to the next
+        self.findn_between(haystack, nn, start, end).into_iter()
+            .map(|byte| {
+                for ch in haystack[pos.byte..byte].chars() {
+                    pos.char += 1;
+                    if ch == '\n' {
+                        pos.line += 1;
+                        pos.column = 1;
+                    } else {
+                        pos.column += 1;
+                    }
+                }
+                pos.byte = byte;
+                pos
+            })
+            .collect()
+    }
+}
+
+impl Collect for StrSearcher<'_> {
+    type Haystack = str;
+    type Match = usize;
+
+    fn haystack_len(haystack: &str) -> usize {
+        haystack.len()
+    }
+
+    // check or snap `start` and `end` to char boundaries
+    // (none if there's no boundary between them to snap to)
+    fn bounds(&self, haystack: &str,
+              start: usize, end: usize)
+              -> Result<Option<(usize, usize)>, SearchError> {
+        check_range(haystack.len(), start, end)?;
+
+        if !self.snap {
+            for offset in [start, end] {
+                if !haystack.is_char_boundary(offset) {
+                    return Err(SearchError::NotCharBoundary(offset));
+                }
+            }
+            return Ok(Some((start, end)));
+        }
+
+        let mut start = start;
+        while !haystack.is_char_boundary(start) { start += 1; }
+
+        let mut end = end;
+        while !haystack.is_char_boundary(end) { end -= 1; }
+
+        // both within the same character
+        if start > end {
+            return Ok(None);
+        }
+
+        Ok(Some((start, end)))
+    }
+
+    fn collect(&self, haystack: &str, nn: usize,
+               start: usize, end: usize) -> Vec<usize> {
+        findn_str_between(haystack, self.needle, nn, start, end)
+    }
+}
@@ -0,0 +1,197 @@
+use super::{boyer_moore_shift, boyer_moore_unmatched_chars};
+use super::matches::Scan;
+
+/// A needle in which some positions (`None`) match any byte
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WildcardPattern {
+    symbols: Vec<Option<u8>>,
+}
+
+impl WildcardPattern {
+    /// A pattern of bytes and wildcards
+    pub fn new(symbols: Vec<Option<u8>>) -> WildcardPattern {
+        WildcardPattern { symbols }
+    }
+
+    /// Parse a signature of hex bytes and `??` wildcards
+    /// separated by whitespace, such as `"DE AD ?? EF"`
+    pub fn parse_hex(signature: &str) -> Option<WildcardPattern> {
+        let symbols = signature.split_whitespace()
+            .map(|token| match token {
+                "?" | "??" => Some(None),
+                _ if token.len() == 2 =>
+                    u8::from_str_radix(token, 16).ok().map(Some),
+                _ => None,
+            })
+            .collect::<Option<Vec<Option<u8>>>>()?;
+
+        Some(WildcardPattern { symbols })
+    }
+
+    /// The bytes and wildcards of the pattern
+    pub fn symbols(&self) -> &[Option<u8>] {
+        &self.symbols
+    }
+
+    /// The number of positions in the pattern
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Whether the pattern has no positions
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+/// A Boyer-Moore searcher for a needle with wildcards,
+/// whose tables only shift as far as every wildcard allows
+#[derive(Clone, Debug)]
+pub struct WildcardBoyerMoore<'p> {
+    pattern: &'p [Option<u8>],
+    ct: Vec<usize>,
+    pt: Vec<usize>,
+    overlapping: bool,
+}
+
+impl<'p> WildcardBoyerMoore<'p> {
+    /// Compute the Boyer-Moore tables for `pattern`
+    pub fn new(pattern: &'p WildcardPattern) -> WildcardBoyerMoore<'p> {
+        let pattern = pattern.symbols();
+
+        // no tables for an empty needle, which matches immediately
+        if pattern.is_empty() {
+            return WildcardBoyerMoore {
+                pattern, ct: Vec::new(), pt: Vec::new(), overlapping: false,
+            };
+        }
+
+        WildcardBoyerMoore {
+            pattern,
+            ct: wildcard_unmatched_chars(pattern),
+            pt: wildcard_matching_suffixes(pattern),
+            overlapping: false,
+        }
+    }
+
+    /// Also report matches which overlap earlier ones,
+    /// shifting by the pattern's period after each match
+    pub fn overlapping(mut self, overlapping: bool) -> WildcardBoyerMoore<'p> {
+        self.overlapping = overlapping;
+        self
+    }
+}
+
+impl Scan for WildcardBoyerMoore<'_> {
+    fn needle_len(&self) -> usize {
+        self.pattern.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        let nlen = self.pattern.len();
+
+        // step up through the haystack
+        let mut outerii = from;
+        while outerii + nlen <= end {
+
+            // step back through the pattern,
+            // skipping over wildcards
+            let mut windowii = nlen;
+            loop {
+                if windowii == 0 {
+                    return Some(outerii);
+                }
+                windowii -= 1;
+
+                let ch = haystack[outerii + windowii];
+                match self.pattern[windowii] {
+                    Some(byte) if byte != ch => {
+                        // no match or a partial match
+                        outerii += boyer_moore_shift(&self.ct, &self.pt, nlen,
+                                                     windowii, ch);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        None
+    }
+
+    fn shift_after_match(&self) -> usize {
+        if self.overlapping {
+            self.pt[self.pattern.len() - 1]
+        } else {
+            self.pattern.len()
+        }
+    }
+}
+
+// the bad-character table, where a wildcard is an occurrence of every byte
+fn wildcard_unmatched_chars(pattern: &[Option<u8>]) -> Vec<usize> {
+    let len = pattern.len();
+
+    // fill in wildcards with any byte,
+    // which can shift no further than the last wildcard anyway
+    let bytes: Vec<u8> = pattern.iter().map(|symbol| symbol.unwrap_or(0)).collect();
+    let mut deltas = boyer_moore_unmatched_chars(&bytes);
+
+    if let Some(last) = pattern[..len - 1].iter().rposition(Option::is_none) {
+        for delta in deltas.iter_mut() {
+            *delta = (*delta).min(len - 1 - last);
+        }
+    }
+
+    deltas
+}
+
+// the good-suffix table, indexed like `boyer_moore_matching_suffixes`,
+// where a wildcard may line up with anything:
+// for each shift, find how much of the suffix stays compatible,
+// and use it for every matched suffix within that
+fn wildcard_matching_suffixes(pattern: &[Option<u8>]) -> Vec<usize> {
+    let len = pattern.len();
+
+    let compatible = |aa: Option<u8>, bb: Option<u8>| {
+        aa.is_none() || bb.is_none() || aa == bb
+    };
+
+    // shifting past the whole needle is always safe
+    let mut deltas = vec![len; len];
+
+    for shift in 1..len {
+
+        // how many positions from the end stay compatible after the shift
+        let mut matched = 0;
+        while matched < len - shift
+              && compatible(pattern[len - 1 - matched],
+                            pattern[len - 1 - matched - shift])
+        {
+            matched += 1;
+        }
+        if matched == len - shift {
+            matched = len;
+        }
+
+        // keeping the least shift found for each
+        let within = matched.min(len - 1);
+        for (matched_so_far, delta) in deltas[..=within].iter_mut().enumerate() {
+            if *delta < len {
+                continue;
+            }
+
+            // the mismatched position can't line up with the same byte
+            let pos = len - 1 - matched_so_far;
+            if pos >= shift {
+                if let (Some(aa), Some(bb)) = (pattern[pos], pattern[pos - shift]) {
+                    if aa == bb { continue; }
+                }
+            }
+
+            *delta = shift;
+        }
+    }
+
+    deltas
+}
@@ -0,0 +1,152 @@
+use super::matches::{Resume, Scan, Searcher};
+
+/// Returns up to `nn` byte positions of matched substrings
+/// between `start` and `end`
+/// (using the Z algorithm, in one pass over the haystack)
+pub fn z_algorithm_search(haystack: &str, needle: &str,
+                          nn: usize,
+                          start: usize, end: usize) -> Vec<usize> {
+    z_algorithm_search_bytes(haystack, needle, nn, start, end)
+}
+
+/// the same, but over any bytes
+pub fn z_algorithm_search_bytes<H, N>(haystack: &H, needle: &N,
+                                      nn: usize,
+                                      start: usize, end: usize) -> Vec<usize>
+    where H: AsRef<[u8]> + ?Sized, N: AsRef<[u8]> + ?Sized
+{
+    ZAlgorithm::new(needle).findn_between(haystack, nn, start, end)
+}
+
+/// the same, but also reporting matches which overlap earlier ones
+pub fn z_algorithm_search_overlapping(haystack: &str, needle: &str,
+                                      nn: usize,
+                                      start: usize, end: usize) -> Vec<usize> {
+    ZAlgorithm::new(needle).overlapping(true)
+        .findn_between(haystack, nn, start, end)
+}
+
+/// Compute the length of the longest prefix of `needle`
+/// which matches at each of its positions
+/// (a.k.a. the Z array; the first is the whole needle)
+pub fn z_algorithm_prefixes<T: Eq>(needle: &[T]) -> Vec<usize> {
+    let len = needle.len();
+    let mut prefixes = vec![0; len];
+    if len == 0 {
+        return prefixes;
+    }
+    prefixes[0] = len;
+
+    // the rightmost match of a prefix found so far, from left up to right
+    let (mut left, mut right) = (0, 0);
+    for ii in 1..len {
+        // start from what's known within that match
+        let mut matched = if ii < right {
+            prefixes[ii - left].min(right - ii)
+        } else {
+            0
+        };
+
+        // and extend past its end
+        while ii + matched < len && needle[ii + matched] == needle[matched] {
+            matched += 1;
+        }
+
+        if ii + matched > right {
+            left = ii;
+            right = ii + matched;
+        }
+        prefixes[ii] = matched;
+    }
+
+    prefixes
+}
+
+/// A Z algorithm searcher.
+///
+/// This measures how much of the needle matches at each position
+/// of the haystack, reusing the needle's Z array within the rightmost
+/// match found so far, so no byte of the haystack is matched twice.
+#[derive(Clone, Debug)]
+pub struct ZAlgorithm<'n> {
+    needle: &'n [u8],
+    prefixes: Vec<usize>,
+    period: usize,
+    overlapping: bool,
+}
+
+impl<'n> ZAlgorithm<'n> {
+    /// Compute the Z array for `needle`,
+    /// which may be a `str` or any other bytes
+    pub fn new<N>(needle: &'n N) -> ZAlgorithm<'n>
+        where N: AsRef<[u8]> + ?Sized
+    {
+        let needle = needle.as_ref();
+        let len = needle.len();
+        let prefixes = z_algorithm_prefixes(needle);
+
+        // the first position where the rest of the needle is a prefix
+        let period = (1..len).find(|&ii| ii + prefixes[ii] == len)
+            .unwrap_or(len);
+
+        ZAlgorithm { needle, prefixes, period, overlapping: false }
+    }
+
+    /// Also report matches which overlap earlier ones,
+    /// shifting by the needle's period after each match
+    pub fn overlapping(mut self, overlapping: bool) -> ZAlgorithm<'n> {
+        self.overlapping = overlapping;
+        self
+    }
+}
+
+impl Scan for ZAlgorithm<'_> {
+    fn needle_len(&self) -> usize {
+        self.needle.len()
+    }
+
+    fn scan(&self, haystack: &[u8], from: usize, end: usize) -> Option<usize> {
+        self.resume(haystack, from, end, &mut Resume::default())
+    }
+
+    fn shift_after_match(&self) -> usize {
+        if self.overlapping { self.period } else { self.needle.len() }
+    }
+
+    fn resume(&self, haystack: &[u8], from: usize, end: usize,
+              state: &mut Resume) -> Option<usize> {
+        let nlen = self.needle.len();
+
+        // the rightmost match of a prefix found so far, from left up to right,
+        // starting with what's left of the last match
+        let (mut left, mut right) = (from, from + state.known);
+
+        // step up through the haystack
+        let mut outerii = from;
+        while outerii + nlen <= end {
+            // start from what's known within that match
+            let mut matched = if outerii < right {
+                self.prefixes[outerii - left].min(right - outerii)
+            } else {
+                0
+            };
+
+            // and extend past its end
+            while matched < nlen && haystack[outerii + matched] == self.needle[matched] {
+                matched += 1;
+            }
+            if matched == nlen {
+                state.known = nlen - self.shift_after_match();
+                return Some(outerii);
+            }
+
+            if outerii + matched > right {
+                left = outerii;
+                right = outerii + matched;
+            }
+            outerii += 1;
+        }
+
+        None
+    }
+}
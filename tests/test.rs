@@ -0,0 +1,1131 @@
+use search::{find_str, find_str_between, findn_str, findn_str_between, Finder, ReverseSearcher,
+             Searcher};
+
+#[test]
+fn test_findn_str_between() {
+  let data = "abcabc";
+  assert_eq!(findn_str_between(data, "ab", 2, 0, 6), [0, 3]);
+  assert_eq!(findn_str_between(data, "ab", 1, 0, 6), [0]);
+  assert!(findn_str_between(data, "ax", 1, 0, 6).is_empty());
+}
+
+#[test]
+fn test_simple_search() {
+  let data = "abcabc";
+  assert_eq!(search::simple_search(data, "ab", 2, 0, 6), [0, 3]);
+  assert_eq!(search::simple_search(data, "ab", 1, 0, 6), [0]);
+  assert!(search::simple_search(data, "ax", 1, 0, 6).is_empty());
+}
+
+#[test]
+fn test_boyer_moore_search() {
+  let data = "abcabc";
+  assert_eq!(search::boyer_moore_search(data, "ab", 2, 0, 6), [0, 3]);
+  assert_eq!(search::boyer_moore_search(data, "ab", 1, 0, 6), [0]);
+  assert!(search::boyer_moore_search(data, "ax", 1, 0, 6).is_empty());
+}
+
+#[test]
+fn test_bmh_search() {
+  let data = "abcabc";
+  assert_eq!(search::boyer_moore_horspool_search(data, "ab", 2, 0, 6), [0, 3]);
+  assert_eq!(search::boyer_moore_horspool_search(data, "ab", 1, 0, 6), [0]);
+  assert!(search::boyer_moore_horspool_search(data, "ax", 1, 0, 6).is_empty());
+}
+
+#[test]
+fn test_findn_str() {
+  assert!(findn_str("banana", "apple pie", 1).is_empty());
+  assert_eq!([0],    *findn_str("abcxxxxxx", "abc", 1));
+  assert_eq!([3],    *findn_str("xxxabcxxx", "abc", 1));
+  assert_eq!([6],    *findn_str("xxxxxxabc", "abc", 1));
+  assert_eq!([3],    *findn_str("xxxabcabc", "abc", 1));
+  assert_eq!([3, 6], *findn_str("xxxabcabc", "abc", 5));
+  assert_eq!([3, 7], *findn_str("xxxabcxabc", "abc", 5));
+  assert_eq!([3, 8], *findn_str("xxxabcxxabc", "abc", 5));
+}
+
+
+#[test]
+fn test_unmatched_chars_ascii() {
+  let ct = search::boyer_moore_unmatched_chars(b"ANPANMAN");
+
+  assert_eq!(1, ct[b'A' as usize]);
+  assert_eq!(2, ct[b'M' as usize]);
+  assert_eq!(3, ct[b'N' as usize]);
+  assert_eq!(5, ct[b'P' as usize]);
+
+  // others
+  assert_eq!(8, ct[b'z' as usize]);
+  assert_eq!(8, ct[b'w' as usize]);
+  assert_eq!(8, ct[b'x' as usize]);
+}
+
+#[test]
+fn test_unmatched_chars_utf8() {
+  let ct = search::boyer_moore_unmatched_chars("ะเ".as_bytes()); //e0b8b0 e0b980
+
+  assert_eq!(2, ct[0xe0]);
+  assert_eq!(4, ct[0xb8]);
+  assert_eq!(3, ct[0xb0]);
+  assert_eq!(2, ct[0xe0]);
+  assert_eq!(1, ct[0xb9]);
+  assert_eq!(6, ct[0x80]);
+}
+
+#[test]
+fn test_boyer_moore_largest_suffixes() {
+  assert!(search::boyer_moore_largest_suffixes(b"").is_empty());
+
+  assert_eq!(search::boyer_moore_largest_suffixes(b"x"),
+             [1]);
+
+  assert_eq!(search::boyer_moore_largest_suffixes(b"heyyheyyheyy"),
+             [0, 0, 1, 4, 0, 0, 1, 8, 0, 0, 1, 12]);
+
+  assert_eq!(search::boyer_moore_largest_suffixes(b"gcagagag"),
+             [1, 0, 0, 2, 0, 4, 0, 8]);
+}
+
+#[test]
+fn test_matching_suffixes_ascii() {
+  assert!(search::boyer_moore_matching_suffixes(b"").is_empty());
+
+  let test1 = search::boyer_moore_matching_suffixes(b"gcagagag");
+  assert_eq!(test1, [1, 7, 4, 7, 2, 7, 7, 7]);
+
+
+  let pt = search::boyer_moore_matching_suffixes(b"ANPANMAN");
+
+  assert_eq!(1, pt[0]); //        (n)
+  assert_eq!(8, pt[1]); //       (a)n
+  assert_eq!(3, pt[2]); //      (m)an
+  assert_eq!(6, pt[3]); //     (n)man
+  assert_eq!(6, pt[4]); //    (a)nman
+  assert_eq!(6, pt[5]); //   (p)anman
+  assert_eq!(6, pt[6]); //  (n)panman
+  assert_eq!(6, pt[7]); // (a)npanman
+}
+
+#[test]
+fn test_matching_suffixes_utf8() {
+  let pt = search::boyer_moore_matching_suffixes("ประเ".as_bytes());
+
+  assert_eq!( 1, pt[0]);
+  assert_eq!(12, pt[3]);
+  assert_eq!(12, pt[6]);
+  assert_eq!(12, pt[9]);
+}
+
+#[test]
+fn test_find_str() {
+  assert_eq!(find_str("banana", "apple pie"), None);
+  assert_eq!(find_str("", ""), Some(0));
+
+  let data = "ประเทศไทย中华Việt Nam";
+  assert_eq!(find_str(data, ""),     Some( 0));
+  assert_eq!(find_str(data, "ประเ"), Some( 0));
+  assert_eq!(find_str(data, "ะเ"),   Some( 6));
+  assert_eq!(find_str(data, "中华"), Some(27));
+  assert_eq!(find_str(data, "ไท华"), None);
+}
+
+#[test]
+fn test_find_str_between_ascii() {
+  assert_eq!(find_str_between("", "", 0, 0), Some(0));
+  assert_eq!(find_str_between("", "pow", 0, 0), None);
+  assert_eq!(find_str_between("donatello", "don", 0, 9), Some(0));
+  assert_eq!(find_str_between("don", "donatello", 0, 3), None);
+
+  let data = "abcabc";
+  assert_eq!(find_str_between(data, "ab", 0, 6), Some(0));
+  assert_eq!(find_str_between(data, "ab", 2, 6), Some(3));
+  assert_eq!(find_str_between(data, "ab", 2, 4), None);
+}
+
+#[test]
+fn test_find_str_between_utf8() {
+  let data = "ประเทศไทย中华Việt Nam".repeat(2);
+  let data = data.as_str();
+  assert_eq!(find_str_between(data, "", 0, 43), Some(0));
+  assert_eq!(find_str_between(data, "", 6, 43), Some(6));
+
+  assert_eq!(find_str_between(data, "ประ", 0, 43), Some( 0));
+  assert_eq!(find_str_between(data, "ทศไ", 0, 43), Some(12));
+  assert_eq!(find_str_between(data, "ย中", 0, 43), Some(24));
+  assert_eq!(find_str_between(data, "iệt", 0, 43), Some(34));
+  assert_eq!(find_str_between(data, "Nam", 0, 43), Some(40));
+
+  assert_eq!(find_str_between(data, "ประ", 43, 86), Some(43));
+  assert_eq!(find_str_between(data, "ทศไ", 43, 86), Some(55));
+  assert_eq!(find_str_between(data, "ย中", 43, 86), Some(67));
+  assert_eq!(find_str_between(data, "iệt", 43, 86), Some(77));
+  assert_eq!(find_str_between(data, "Nam", 43, 86), Some(83));
+}
+
+#[test]
+fn test_find_str_ascii() {
+  assert_eq!(Some(0), find_str("", ""));
+  assert_eq!(None,    find_str("banana", "apple pie"));
+  assert_eq!(Some(0), find_str("abcxxxxxx", "abc"));
+  assert_eq!(Some(3), find_str("xxxabcxxx", "abc"));
+  assert_eq!(Some(6), find_str("xxxxxxabc", "abc"));
+}
+
+#[test]
+fn test_find_str_utf8() {
+  let data = "ประเทศไทย中华Việt Nam";
+
+  assert_eq!(Some( 0), find_str(data, ""));
+  assert_eq!(None,     find_str(data, "ไท华"));
+  assert_eq!(Some( 0), find_str(data, "ประเ"));
+  assert_eq!(Some( 3), find_str(data, "ระ"));
+  assert_eq!(Some( 6), find_str(data, "ะเ"));
+  assert_eq!(Some(15), find_str(data, "ศไทย中华"));
+  assert_eq!(Some(18), find_str(data, "ไทย中华"));
+  assert_eq!(Some(24), find_str(data, "ย中华"));
+  assert_eq!(Some(27), find_str(data, "中华"));
+}
+
+#[test]
+fn test_boyer_moore_searcher() {
+  let bm = search::BoyerMoore::new("abc");
+
+  assert_eq!(bm.find("xxxabcabc"), Some(3));
+  assert_eq!(bm.find("banana"),    None);
+  assert_eq!(bm.find_between("xxxabcabc", 4, 9), Some(6));
+  assert_eq!(bm.find_n("xxxabcxabc", 1), [3]);
+  assert_eq!(bm.find_all("xxxabcxabc"),  [3, 7]);
+
+  // the same tables, reused on another haystack
+  assert_eq!(bm.find_all("abcxxabc"), [0, 5]);
+}
+
+#[test]
+fn test_horspool_searcher() {
+  let bmh = search::Horspool::new("abc");
+
+  assert_eq!(bmh.find("xxxabcabc"), Some(3));
+  assert_eq!(bmh.find("banana"),    None);
+  assert_eq!(bmh.find_between("xxxabcabc", 4, 9), Some(6));
+  assert_eq!(bmh.find_n("xxxabcxabc", 1), [3]);
+  assert_eq!(bmh.find_all("xxxabcxabc"),  [3, 7]);
+
+  // the same table, reused on another haystack
+  assert_eq!(bmh.find_all("abcxxabc"), [0, 5]);
+}
+
+#[test]
+fn test_overlapping_search() {
+  assert_eq!(search::simple_search("aaaa", "aa", 5, 0, 4), [0, 2]);
+  assert_eq!(search::simple_search_overlapping("aaaa", "aa", 5, 0, 4),
+             [0, 1, 2]);
+  assert_eq!(search::boyer_moore_search_overlapping("aaaa", "aa", 5, 0, 4),
+             [0, 1, 2]);
+  assert_eq!(search::boyer_moore_horspool_search_overlapping("aaaa", "aa", 5, 0, 4),
+             [0, 1, 2]);
+
+  let data = "xabababax";
+  assert_eq!(search::simple_search_overlapping(data, "aba", 5, 0, 9), [1, 3, 5]);
+  assert_eq!(search::boyer_moore_search_overlapping(data, "aba", 5, 0, 9), [1, 3, 5]);
+  assert_eq!(search::boyer_moore_horspool_search_overlapping(data, "aba", 5, 0, 9),
+             [1, 3, 5]);
+
+  // limited by nn
+  assert_eq!(search::boyer_moore_search_overlapping(data, "aba", 2, 0, 9), [1, 3]);
+}
+
+#[test]
+fn test_overlapping_searchers() {
+  let data = "gcagagagcagagag";
+
+  let bm = search::BoyerMoore::new("gcagagag").overlapping(true);
+  assert_eq!(bm.find_all(data), [0, 7]);
+  assert_eq!(search::BoyerMoore::new("gcagagag").find_all(data), [0]);
+
+  let bmh = search::Horspool::new("agag").overlapping(true);
+  assert_eq!(bmh.find_all(data), [2, 4, 9, 11]);
+  assert_eq!(search::Horspool::new("agag").find_all(data), [2, 9]);
+}
+
+#[test]
+fn test_matches_iterator() {
+  let bm = search::BoyerMoore::new("abc");
+  let mut matches = bm.matches("xxxabcxabcxxabc");
+
+  assert_eq!(matches.next(), Some(3));
+  assert_eq!(matches.next(), Some(7));
+  assert_eq!(matches.next(), Some(12));
+  assert_eq!(matches.next(), None);
+  assert_eq!(matches.next(), None);
+
+  let bmh = search::Horspool::new("abc");
+  assert_eq!(bmh.matches("xxxabcxabcxxabc").skip(1).collect::<Vec<_>>(), [7, 12]);
+  assert_eq!(bmh.matches_between("xxxabcxabcxxabc", 4, 12).count(), 1);
+  assert_eq!(bmh.matches("banana").next(), None);
+
+  // empty needle
+  let empty = search::BoyerMoore::new("");
+  assert_eq!(empty.matches_between("abc", 1, 3).collect::<Vec<_>>(), [1]);
+
+  // overlapping
+  let aa = search::Horspool::new("aa").overlapping(true);
+  assert_eq!(aa.matches("aaaa").collect::<Vec<_>>(), [0, 1, 2]);
+}
+
+#[test]
+fn test_reverse_search() {
+  let data = "xxxabcxabcxxabc";
+  assert_eq!(search::boyer_moore_reverse_search(data, "abc", 5, 0, 15), [12, 7, 3]);
+  assert_eq!(search::boyer_moore_reverse_search(data, "abc", 1, 0, 15), [12]);
+  assert_eq!(search::boyer_moore_reverse_search(data, "abc", 5, 0, 12), [7, 3]);
+  assert!(search::boyer_moore_reverse_search(data, "abx", 5, 0, 15).is_empty());
+
+  assert_eq!(search::boyer_moore_horspool_reverse_search(data, "abc", 5, 0, 15), [12, 7, 3]);
+  assert_eq!(search::boyer_moore_horspool_reverse_search(data, "abc", 5, 4, 15), [12, 7]);
+  assert!(search::boyer_moore_horspool_reverse_search(data, "abx", 5, 0, 15).is_empty());
+
+  // non-overlapping, from the right
+  assert_eq!(search::boyer_moore_reverse_search("aaaaa", "aa", 5, 0, 5), [3, 1]);
+}
+
+#[test]
+fn test_reverse_searchers() {
+  let data = "ประเทศไทย中华Việt Nam".repeat(2);
+  let data = data.as_str();
+
+  let bm = search::ReverseBoyerMoore::new("ทศไ");
+  assert_eq!(bm.rfind(data), Some(55));
+  assert_eq!(bm.rfind_between(data, 0, 43), Some(12));
+  assert_eq!(bm.rfind_n(data, 5), [55, 12]);
+  assert_eq!(bm.rfind("banana"), None);
+
+  let bmh = search::ReverseHorspool::new("Nam");
+  assert_eq!(bmh.rfind(data), Some(83));
+  assert_eq!(bmh.rfind_between(data, 0, 43), Some(40));
+  assert_eq!(bmh.rfind_n(data, 1), [83]);
+
+  // empty needle matches at the end
+  assert_eq!(search::ReverseHorspool::new("").rfind(data), Some(86));
+
+  // byte buffers, scanned from the end
+  let buffer: Vec<u8> = vec![0x00, 0xff, 0x0a, 0x00, 0xff, 0x0a, 0x01];
+  assert_eq!(search::ReverseBoyerMoore::new(&[0xff, 0x0a]).rfind(&buffer), Some(4));
+  assert_eq!(search::ReverseHorspool::new(b"\n").rfind_n(&buffer, 5), [5, 2]);
+  assert_eq!(search::ReverseHorspool::new(b"\n").rfind_between(&buffer, 0, 5), Some(2));
+}
+
+#[test]
+fn test_ignore_ascii_case() {
+  let data = "xxxAbCxabcxxABC";
+  assert_eq!(search::boyer_moore_search_ignore_ascii_case(data, "abc", 5, 0, 15), [3, 7, 12]);
+  assert_eq!(search::boyer_moore_search_ignore_ascii_case(data, "ABC", 1, 4, 15), [7]);
+  assert!(search::boyer_moore_search_ignore_ascii_case(data, "abd", 5, 0, 15).is_empty());
+
+  let bm = search::BoyerMooreIgnoreAsciiCase::new("ANPANMAN");
+  assert_eq!(bm.find("the anpanman show"), Some(4));
+  assert_eq!(bm.find_all("AnPaNmAn anpanman"), [0, 9]);
+
+  // non-ASCII bytes are compared exactly
+  assert_eq!(bm.find("ÀNPANMAN"), None);
+  assert_eq!(search::BoyerMooreIgnoreAsciiCase::new("Việt").find("VIệT NAM"), Some(0));
+  assert_eq!(search::BoyerMooreIgnoreAsciiCase::new("Việt").find("VIỆT NAM"), None);
+}
+
+#[test]
+fn test_ignore_case() {
+  let data = "ประเทศไทย中华VIỆT NAM";
+  assert_eq!(search::boyer_moore_search_ignore_case(data, "việt", 5, 0, data.len()), [33]);
+  assert_eq!(search::boyer_moore_search_ignore_case(data, "Nam", 5, 0, data.len()), [40]);
+
+  let bm = search::BoyerMooreIgnoreCase::new("ΣΟΦΊΑ");
+  assert_eq!(bm.find("η σοφία"), Some(3));
+
+  // case folding, not just lowercasing: final sigma, sharp s
+  assert_eq!(search::BoyerMooreIgnoreCase::new("ΟΦΊΑΣ").find("σοφίας"), Some(2));
+  assert_eq!(search::BoyerMooreIgnoreCase::new("strasse").find("STRAßE"), Some(0));
+  assert_eq!(search::BoyerMooreIgnoreCase::new("STRAßE").find("die strasse"), Some(4));
+  assert_eq!(search::BoyerMooreIgnoreCase::new("s").find_all("ß"), []);
+
+  // offsets map back to the original bytes, across changes in length
+  // ('İ' lowercases to two characters, 'K' (Kelvin) to one shorter 'k')
+  let bm = search::BoyerMooreIgnoreCase::new("i̇stanbul");
+  assert_eq!(bm.find_all("İSTANBUL istanbul İstanbul"), [0, 19]);
+  assert_eq!(search::BoyerMooreIgnoreCase::new("k").find_all("\u{212a}k"), [0, 3]);
+
+  // no matches inside the mapping of a single character
+  assert_eq!(search::BoyerMooreIgnoreCase::new("i").find("İ"), None);
+}
+
+#[test]
+fn test_stream_chunks() {
+  let bm = search::BoyerMoore::new("abc");
+  let mut stream = bm.stream();
+
+  // matches split across chunk boundaries
+  assert_eq!(stream.feed(b"xxxa"), []);
+  assert_eq!(stream.feed(b"bcxab"), [3]);
+  assert_eq!(stream.feed(b"c"), [7]);
+  assert_eq!(stream.feed(b""), []);
+  assert_eq!(stream.feed(b"abcabc"), [10, 13]);
+
+  // overlapping, with single-byte chunks
+  let bmh = search::Horspool::new("aa").overlapping(true);
+  let mut stream = bmh.stream();
+  let found: Vec<usize> = b"xaaaa".iter().flat_map(|b| stream.feed(&[*b])).collect();
+  assert_eq!(found, [1, 2, 3]);
+}
+
+#[test]
+fn test_stream_reader() {
+  let data = "xyzzy ".repeat(30000) + "plugh" + &"xyzzy ".repeat(30000) + "plugh";
+
+  let bmh = search::Horspool::new("plugh");
+  assert_eq!(bmh.stream().read_from(data.as_bytes()).unwrap(), [180000, 360005]);
+
+  let bm = search::BoyerMoore::new("zy xy");
+  let found = bm.stream().read_from(data.as_bytes()).unwrap();
+  assert_eq!(found, bm.find_all(&data));
+  assert_eq!(found.len(), 29999 * 2);
+
+  let empty = search::BoyerMoore::new("");
+  assert_eq!(empty.stream().read_from(&b""[..]).unwrap(), [0]);
+}
+
+#[test]
+fn test_wu_manber_search() {
+  let data = "she sells sea shells by the sea shore";
+  let needles = ["sea", "shells", "he", "shore"];
+
+  assert_eq!(search::wu_manber_search(data, &needles, 100, 0, data.len()),
+             [(2, 1), (0, 10), (1, 14), (2, 15), (2, 25), (0, 28), (3, 32)]);
+  assert_eq!(search::wu_manber_search(data, &needles, 2, 0, data.len()),
+             [(2, 1), (0, 10)]);
+  assert_eq!(search::wu_manber_search(data, &needles, 100, 26, 35),
+             [(0, 28)]);
+
+  let wm = search::WuManber::new(&["ทศ", "中华", "Nam"]);
+  assert_eq!(wm.find("ประเทศไทย中华Việt Nam"), Some((0, 12)));
+  assert_eq!(wm.find_all("ประเทศไทย中华Việt Nam"), [(0, 12), (1, 27), (2, 40)]);
+  assert_eq!(wm.find("banana"), None);
+
+  // overlapping needles, and a needle repeated
+  let wm = search::WuManber::new(&["aa", "aaa", "aa"]);
+  assert_eq!(wm.find_all("aaa"), [(0, 0), (1, 0), (2, 0), (0, 1), (2, 1)]);
+
+  // a one-byte needle, and blocks which hash alike
+  assert_eq!(search::WuManber::new(&["a", "bc"]).find_all("abcab"), [(0, 0), (1, 1), (0, 3)]);
+  assert_eq!(search::WuManber::new(&["\u{1}\u{10}"]).find_all("\0\0\u{1}\u{10}"), [(0, 2)]);
+
+  assert_eq!(search::WuManber::new::<&str>(&[]).find_all("abc"), []);
+
+  // byte needles, owned or borrowed
+  let owned = vec![vec![0xffu8, 0], vec![0]];
+  assert_eq!(search::WuManber::new(&owned).find_all(b"\0\xff\0"), [(1, 0), (0, 1), (1, 2)]);
+  assert_eq!(search::wu_manber_search(b"\xfe\xff", &[b"\xff"], 5, 0, 2), [(0, 1)]);
+  let strings = vec![String::from("ab"), String::from("b")];
+  assert_eq!(search::WuManber::try_new(&strings).unwrap().find_all("abab").len(), 4);
+}
+
+#[test]
+fn test_choose_algorithm() {
+  let thresholds = search::Thresholds::default();
+  let long = 100000;
+
+  assert_eq!(thresholds.choose(long, b"x"), search::Algorithm::SingleByte);
+  assert_eq!(thresholds.choose(long, b"ab"), search::Algorithm::Horspool);
+  assert_eq!(thresholds.choose(long, b"needle"), search::Algorithm::Horspool);
+  assert_eq!(thresholds.choose(long, b"gattacagattaca"), search::Algorithm::Horspool);
+
+  // short haystacks
+  assert_eq!(thresholds.choose(20, b"needle"), search::Algorithm::Simple);
+  assert_eq!(thresholds.choose(159, b"needle"), search::Algorithm::Simple);
+  assert_eq!(thresholds.choose(160, b"needle"), search::Algorithm::Horspool);
+  assert_eq!(thresholds.choose(200, &[b'z'; 201]), search::Algorithm::Simple);
+
+  // Boyer-Moore, once allowed, for needles of few distinct bytes
+  let dna = search::Thresholds { boyer_moore_max_alphabet: 4, ..thresholds };
+  assert_eq!(dna.choose(long, b"gattacagattaca"), search::Algorithm::BoyerMoore);
+  assert_eq!(dna.choose(long, b"gattaca"), search::Algorithm::Horspool);
+
+  // no overflow scaling the needle length
+  let huge = search::Thresholds { min_needle_lengths: usize::MAX, ..thresholds };
+  assert_eq!(huge.choose(long, b"needle"), search::Algorithm::Simple);
+
+  // overridden by the caller
+  let horspool_only = search::Thresholds {
+    min_haystack: 0,
+    min_needle_lengths: 0,
+    min_needle: 0,
+    boyer_moore_max_alphabet: 0,
+    ..thresholds
+  };
+  assert_eq!(horspool_only.choose(20, b"ab"), search::Algorithm::Horspool);
+  assert_eq!(horspool_only.choose(long, b"gattacagattaca"), search::Algorithm::Horspool);
+}
+
+#[test]
+fn test_adaptive_search() {
+  let data = "xyzzy ".repeat(1000) + "gattacagattaca plugh x";
+  let data = data.as_str();
+
+  assert_eq!(findn_str(data, "x", 2), [0, 6]);
+  assert_eq!(find_str(data, "plugh"), Some(6015));
+  assert_eq!(find_str(data, "gattacagattaca"), Some(6000));
+  assert_eq!(find_str_between(data, "x", 6001, data.len()), Some(6021));
+
+  let bm_only = search::Thresholds {
+    boyer_moore_max_alphabet: 256,
+    boyer_moore_min_needle: 0,
+    ..search::Thresholds::default()
+  };
+  assert_eq!(search::findn_str_between_with(&bm_only, data, "zy x", 2, 0, data.len()),
+             [3, 9]);
+
+  for algorithm in [search::Algorithm::Simple, search::Algorithm::Horspool,
+                    search::Algorithm::BoyerMoore] {
+    assert_eq!(algorithm.search(data, "plugh", 1, 0, data.len()), [6015]);
+  }
+  assert_eq!(search::single_byte_search(data, "p", 5, 0, data.len()), [6015]);
+
+  // any other needle is searched for in full
+  assert_eq!(search::Algorithm::SingleByte.search(data, "plugh", 5, 0, data.len()), [6015]);
+  assert_eq!(search::single_byte_search("bcbc", "bc", 5, 0, 4), [0, 2]);
+  assert_eq!(search::single_byte_search("bcbc", "", 5, 1, 4), [1]);
+}
+
+#[test]
+fn test_turbo_boyer_moore_search() {
+  let data = "abcabc";
+  assert_eq!(search::turbo_boyer_moore_search(data, "ab", 2, 0, 6), [0, 3]);
+  assert_eq!(search::turbo_boyer_moore_search(data, "ab", 1, 0, 6), [0]);
+  assert!(search::turbo_boyer_moore_search(data, "ax", 1, 0, 6).is_empty());
+
+  // periodic inputs
+  let data = "a".repeat(1000) + "b";
+  let needle = "a".repeat(100) + "b";
+  assert_eq!(search::turbo_boyer_moore_search(&data, &needle, 5, 0, data.len()), [900]);
+
+  let turbo = search::TurboBoyerMoore::new("gcagagag").overlapping(true);
+  assert_eq!(turbo.find_all("gcatcgcagagagtatacagtacggcagagagcagagag"), [5, 24, 31]);
+  assert_eq!(turbo.find_between("gcagagagcagagag", 1, 15), Some(7));
+}
+
+#[test]
+fn test_apostolico_giancarlo_search() {
+  let data = "abcabc";
+  assert_eq!(search::apostolico_giancarlo_search(data, "ab", 2, 0, 6), [0, 3]);
+  assert_eq!(search::apostolico_giancarlo_search(data, "ab", 1, 0, 6), [0]);
+  assert!(search::apostolico_giancarlo_search(data, "ax", 1, 0, 6).is_empty());
+
+  // periodic inputs
+  let data = "a".repeat(1000) + "b";
+  let needle = "a".repeat(100) + "b";
+  assert_eq!(search::apostolico_giancarlo_search(&data, &needle, 5, 0, data.len()), [900]);
+
+  let ag = search::ApostolicoGiancarlo::new("aaa").overlapping(true);
+  assert_eq!(ag.find_all("aaaaa"), [0, 1, 2]);
+  assert_eq!(search::ApostolicoGiancarlo::new("aaa").find_all("aaaaa"), [0]);
+  assert_eq!(ag.find("ประเทศไทย"), None);
+}
+
+#[test]
+fn test_unmatched_chars_binary() {
+  let ct = search::boyer_moore_unmatched_chars(&[0xff, 0x00, 0xfe, 0xff]);
+
+  assert_eq!(256, ct.len());
+  assert_eq!(3, ct[0xff]);
+  assert_eq!(2, ct[0x00]);
+  assert_eq!(1, ct[0xfe]);
+  assert_eq!(4, ct[0x7f]);
+
+  // searching binary data, with every byte value
+  let data: Vec<u8> = (0..=255u8).chain(0..=255u8).collect();
+  let bm = search::BoyerMoore::new("");
+  assert_eq!(bm.stream().feed(&data), [0]);
+
+  let needle = "\u{7f}";
+  let bmh = search::Horspool::new(needle);
+  assert_eq!(bmh.stream().feed(&data), [0x7f, 0x17f]);
+
+  // a needle with the last byte value, in a searcher's tables
+  let needle = b"\xff\x00\xff";
+  let haystack = b"\xff\xff\x00\xff\x00\xff\xfe\xff\x00\xff";
+  assert_eq!(search::BoyerMoore::new(needle).find(haystack), Some(1));
+  assert_eq!(search::BoyerMoore::new(needle).find_all(haystack), [1, 7]);
+  assert_eq!(search::BoyerMoore::new(needle).overlapping(true).find_all(haystack), [1, 3, 7]);
+  assert_eq!(search::Horspool::new(needle).find_all(haystack), [1, 7]);
+  assert_eq!(search::Horspool::new(needle).find_all(&data), []);
+}
+
+#[test]
+fn test_unmatched_table() {
+  let bytes = search::UnmatchedTable::new(b"ANPANMAN");
+  assert_eq!(1, bytes.get(b'A'));
+  assert_eq!(5, bytes.get(b'P'));
+  assert_eq!(8, bytes.get(0xff));
+
+  let wide = search::UnmatchedTable::new(&[0xfffeu16, 7, 0xffff, 7]);
+  assert_eq!(2, wide.get(7));
+  assert_eq!(1, wide.get(0xffff));
+  assert_eq!(3, wide.get(0xfffe));
+  assert_eq!(4, wide.get(8));
+  // searching with it across the whole u16 range
+  let needle = [0xfffeu16, 7, 0xffff, 7];
+  let haystack = [7u16, 0xfffe, 7, 0xffff, 7, 0xfffe, 7, 0xffff, 7, 0];
+  assert_eq!(search::GenericBoyerMoore::with_symbols(&needle).find_all(&haystack), vec![1, 5]);
+
+  let codepoints = search::UnmatchedTable::new(&[0x10ffffu32, 0x1f600, 0x1f600]);
+  assert_eq!(1, codepoints.get(0x1f600));
+  assert_eq!(2, codepoints.get(0x10ffff));
+  assert_eq!(3, codepoints.get(u32::MAX));
+
+  let chars: Vec<char> = "中华中".chars().collect();
+  let chars = search::UnmatchedTable::new(&chars);
+  assert_eq!(1, chars.get('华'));
+  assert_eq!(2, chars.get('中'));
+  assert_eq!(3, chars.get('x'));
+}
+
+#[test]
+fn test_bytes_search() {
+  let data: Vec<u8> = vec![0xde, 0xad, 0xbe, 0xef, 0x00, 0xff, 0xde, 0xad, 0xbe, 0xef];
+  let needle = [0xbe, 0xef];
+
+  assert_eq!(search::simple_search_bytes(&data, &needle, 5, 0, data.len()), [2, 8]);
+  assert_eq!(search::boyer_moore_search_bytes(&data, &needle, 5, 0, data.len()), [2, 8]);
+  assert_eq!(search::boyer_moore_horspool_search_bytes(&data, &needle, 5, 0, data.len()),
+             [2, 8]);
+  assert_eq!(search::boyer_moore_search_bytes(&data[..], b"\x00\xff", 5, 0, data.len()), [4]);
+  assert!(search::boyer_moore_horspool_search_bytes(&data, b"\xff\xff", 5, 0, data.len())
+          .is_empty());
+
+  // searchers over bytes, and over str
+  let bm = search::BoyerMoore::new(&needle);
+  assert_eq!(bm.find(&data), Some(2));
+  assert_eq!(bm.find_between(&data, 3, 10), Some(8));
+  assert_eq!(bm.find_all(&data[..]), [2, 8]);
+  assert_eq!(bm.matches(&data).count(), 2);
+
+  let bmh = search::Horspool::new(b"\xff\xde");
+  assert_eq!(bmh.find_n(&data, 5), [5]);
+  assert_eq!(bmh.find("text"), None);
+
+  let owned = String::from("xxabc");
+  assert_eq!(search::Horspool::new(&String::from("abc")).find(&owned), Some(2));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Base { A, C, G, T }
+
+impl search::Symbol for Base {
+  const ALPHABET_SIZE: Option<usize> = Some(4);
+
+  fn index(self) -> usize { self as usize }
+}
+
+#[test]
+fn test_generic_search() {
+  // tokens
+  let words: Vec<&str> = "the cat sat on the mat and the cat ran".split(' ').collect();
+  let phrase = ["the", "cat"];
+  assert_eq!(search::generic_boyer_moore_search(&words, &phrase, 5, 0, words.len()), [0, 7]);
+  assert_eq!(search::generic_boyer_moore_search(&words, &phrase, 5, 1, words.len()), [7]);
+  assert_eq!(search::GenericBoyerMoore::new(&["mat"]).find(&words), Some(5));
+  assert_eq!(search::GenericBoyerMoore::new(&["cat", "cat"]).find(&words), None);
+
+  // codepoints, in a hashed table and a sparse symbol table
+  let text: Vec<u32> = "ünïcödé ünï".chars().map(|ch| ch as u32).collect();
+  let needle: Vec<u32> = "ünï".chars().map(|ch| ch as u32).collect();
+  assert_eq!(search::GenericBoyerMoore::new(&needle).find_all(&text), [0, 8]);
+  assert_eq!(search::GenericBoyerMoore::with_symbols(&needle).find_all(&text), [0, 8]);
+
+  // a small alphabet in a dense table
+  use Base::*;
+  let dna = [G, C, A, G, A, G, A, G, T, G, C, A, G, A, G, A, G];
+  let bm = search::GenericBoyerMoore::with_symbols(&[G, A, G]);
+  assert_eq!(bm.find_all(&dna), [3, 12]);
+  assert_eq!(bm.find_n(&dna, 1), [3]);
+  assert_eq!(bm.find_between(&dna, 4, dna.len()), Some(5));
+  assert_eq!(search::GenericBoyerMoore::with_symbols(&[]).find_between(&dna, 4, 6), Some(4));
+
+  // bytes agree with the str search
+  let data = "GCATCGCAGAGAGTATACAGTACG";
+  let generic = search::generic_boyer_moore_search(data.as_bytes(), b"GCAGAGAG", 5, 0, data.len());
+  assert_eq!(generic, search::boyer_moore_search(data, "GCAGAGAG", 5, 0, data.len()));
+}
+
+#[test]
+fn test_str_searcher_utf8() {
+  let data = "ประเทศไทย中华Việt Nam";
+
+  let han = search::StrSearcher::new("华");
+  assert_eq!(han.find(data), Some(30));
+  assert_eq!(han.snap(true).find_between(data, 28, 33), Some(30));
+  assert_eq!(han.snap(true).find_between(data, 28, 32), None);
+  // nothing reported outside the range, even for an empty needle
+  assert_eq!(search::StrSearcher::new("").snap(true).find_between(data, 28, 29), None);
+  assert_eq!(search::StrSearcher::new("").snap(true).find_between(data, 28, 30), Some(30));
+
+  let nam = search::StrSearcher::new("Nam");
+  assert_eq!(nam.findn_chars_between(data, 5, 0, data.len()), [16]);
+
+  let lines = "中华\nViệt Nam\n中华";
+  let positions = search::StrSearcher::new("中华").positions(lines);
+  assert_eq!(positions, [
+    search::Position { byte: 0, char: 0, line: 1, column: 1 },
+    search::Position { byte: 18, char: 12, line: 3, column: 1 },
+  ]);
+  assert_eq!(search::StrSearcher::new("Nam").positions(lines),
+             [search::Position { byte: 14, char: 8, line: 2, column: 6 }]);
+}
+
+#[test]
+#[should_panic]
+fn test_str_searcher_not_char_boundary() {
+  let data = "ประเทศไทย中华Việt Nam";
+  search::StrSearcher::new("华").find_between(data, 28, 33);
+}
+
+#[test]
+fn test_finder() {
+  // every searcher takes anything which borrows as its haystack,
+  // and finds all matches however many there are
+  let ones = vec![1u32; 300];
+  assert_eq!(search::GenericBoyerMoore::new(&[1]).find_all(&ones).len(), 300);
+  assert_eq!(search::WuManber::new(&["a"]).find_all(b"aaa"), vec![(0, 0), (0, 1), (0, 2)]);
+  assert_eq!(search::TarhioUkkonen::new("ab", 1).find(&String::from("xb")), Some((0, 1)));
+  assert_eq!(search::StrSearcher::new("a").find_n(&String::from("aaa"), 2), vec![0, 1]);
+  assert_eq!(search::BoyerMooreIgnoreCase::new("A").find_between("aaa", 1, 3), Some(1));
+  assert_eq!(search::ReverseHorspool::new("a").rfind_n(b"aaa", 2), vec![2, 1]);
+}
+
+#[test]
+fn test_try_search() {
+  use search::SearchError;
+
+  let data = "abcabc";
+  assert_eq!(search::try_boyer_moore_search(data, "ab", 2, 0, 6), Ok(vec![0, 3]));
+  assert_eq!(search::try_boyer_moore_search(data, "ab", 2, 4, 3),
+             Err(SearchError::InvalidRange { start: 4, end: 3, len: 6 }));
+  assert_eq!(search::try_boyer_moore_horspool_search(data, "ab", 2, 0, 7),
+             Err(SearchError::InvalidRange { start: 0, end: 7, len: 6 }));
+  assert_eq!(search::try_simple_search(data, "bc", 2, 2, 6), Ok(vec![4]));
+  assert_eq!(search::try_find_str_between(data, "ca", 0, 6), Ok(Some(2)));
+  assert!(search::try_findn_str_between(data, "ca", 1, 9, 9).is_err());
+  assert_eq!(search::Horspool::new("c").try_findn_between(data, 1, 3, 6), Ok(vec![5]));
+
+  assert_eq!(search::try_boyer_moore_unmatched_chars(b""), Err(SearchError::EmptyNeedle));
+  assert!(search::try_boyer_moore_unmatched_chars(b"ab").is_ok());
+  assert_eq!(search::WuManber::try_new(&["ab", ""]).err(), Some(SearchError::EmptyNeedle));
+
+  let text = "ประเทศไทย中华Việt Nam";
+  let han = search::StrSearcher::new("华");
+  assert_eq!(han.try_findn_between(text, 1, 28, 33), Err(SearchError::NotCharBoundary(28)));
+  assert_eq!(han.try_findn_between(text, 1, 27, 33), Ok(vec![30]));
+  assert_eq!(han.snap(true).try_findn_between(text, 1, 28, 33), Ok(vec![30]));
+  assert_eq!(SearchError::NotCharBoundary(28).to_string(),
+             "byte offset 28 is not on a char boundary");
+
+  // the other searchers' fallible entry points
+  let bad = Err(SearchError::InvalidRange { start: 2, end: 1, len: 3 });
+  assert_eq!(search::ReverseBoyerMoore::new("a").try_rfindn_between("abc", 1, 2, 1), bad);
+  assert_eq!(search::ReverseHorspool::new("c").try_rfindn_between("abc", 1, 0, 3), Ok(vec![2]));
+  assert_eq!(search::GenericBoyerMoore::new(&[1, 2]).try_findn_between(&[1, 2, 3], 1, 2, 1), bad);
+  assert_eq!(search::TarhioUkkonen::new("ab", 1).try_findn_between("abc", 1, 0, 4),
+             Err(SearchError::InvalidRange { start: 0, end: 4, len: 3 }));
+  assert_eq!(search::WuManber::new(&["b"]).try_findn_between("abc", 5, 0, 3), Ok(vec![(0, 1)]));
+  assert!(search::WuManber::new(&["b"]).try_findn_between("abc", 5, 2, 1).is_err());
+  assert!(search::try_findn_str_between_with(&search::Thresholds::default(), "abc", "b", 1, 0, 4)
+            .is_err());
+
+  let ignore_case = search::BoyerMooreIgnoreCase::new("华");
+  assert_eq!(ignore_case.try_findn_between("中华", 1, 1, 6), Err(SearchError::NotCharBoundary(1)));
+  assert_eq!(ignore_case.try_findn_between("中华", 1, 0, 7),
+             Err(SearchError::InvalidRange { start: 0, end: 7, len: 6 }));
+  assert_eq!(ignore_case.try_findn_between("中华", 1, 3, 6), Ok(vec![3]));
+
+  assert_eq!(search::UnmatchedTable::<u16>::try_new(&[]).err(), Some(SearchError::EmptyNeedle));
+  assert!(search::UnmatchedTable::try_new(&[1u16]).is_ok());
+  assert_eq!(search::HashedTable::<u8>::try_new(&[]).err(), Some(SearchError::EmptyNeedle));
+}
+
+#[test]
+fn test_replace() {
+  assert_eq!(search::replace("abcabc", "b", "XX"), "aXXcaXXc");
+  assert_eq!(search::replacen("abcabc", "b", "XX", 1), "aXXcabc");
+  assert_eq!(search::replace_between("abcabc", "b", "", 5, 2, 6), "abcac");
+  assert_eq!(search::replace("中华Việt 中华", "中华", "China"), "ChinaViệt China");
+  assert_eq!(search::replace("abc", "x", "y"), "abc");
+
+  // an empty needle matches at every character boundary, as in std
+  assert_eq!(search::replace("abc", "", "X"), "abc".replace("", "X"));
+  assert_eq!(search::replacen("中华", "", "X", 2), "中华".replacen("", "X", 2));
+  assert_eq!(search::replace_between("abc", "", "X", 5, 1, 2), "aXbXc");
+  assert_eq!(search::BoyerMoore::new("").replace("ab", "X"), b"XaXbX");
+  let mut buffer = b"ab".to_vec();
+  assert_eq!(search::BoyerMoore::new("").replace_in_place(&mut buffer, "X"), 3);
+  assert_eq!(buffer, b"XaXbX");
+
+  // each run of overlapping matches is replaced once
+  let aa = search::Horspool::new("aa");
+  assert_eq!(aa.replace("baaab", "X"), b"bXab");
+  let aa = aa.overlapping(true);
+  assert_eq!(aa.replace("baaab", "X"), b"bXb");
+  assert_eq!(aa.replacen("aaabaa", "X", 1), b"Xbaa");
+
+  let bm = search::BoyerMoore::new("bc");
+  let mut buffer = b"abcabcab".to_vec();
+  assert_eq!(bm.replace_in_place(&mut buffer, ""), 2);
+  assert_eq!(buffer, b"aaab");
+
+  let mut buffer = b"abcabcab".to_vec();
+  assert_eq!(bm.replace_in_place(&mut buffer, "BCD"), 2);
+  assert_eq!(buffer, b"aBCDaBCDab");
+
+  let mut buffer = b"aaaa_aa".to_vec();
+  assert_eq!(search::Horspool::new("aa").overlapping(true).replace_in_place(&mut buffer, "XXX"), 2);
+  assert_eq!(buffer, b"XXX_XXX");
+}
+
+#[test]
+fn test_split() {
+  let data = "id||name||||email";
+  let sep = search::Horspool::new("||");
+
+  let pieces: Vec<&[u8]> = sep.split(data).collect();
+  assert_eq!(pieces, [&b"id"[..], b"name", b"", b"email"]);
+
+  let pieces: Vec<&[u8]> = sep.splitn(data, 2).collect();
+  assert_eq!(pieces, [&b"id"[..], b"name||||email"]);
+  assert_eq!(sep.splitn(data, 0).count(), 0);
+
+  let pieces: Vec<&[u8]> = sep.rsplit(data).collect();
+  assert_eq!(pieces, [&b"email"[..], b"", b"name", b"id"]);
+
+  // the mirrored table is kept for the next rsplit, and for clones
+  let pieces: Vec<&[u8]> = sep.rsplit("a||b").collect();
+  assert_eq!(pieces, [&b"b"[..], b"a"]);
+  assert_eq!(sep.clone().rsplit("a||b").count(), 2);
+
+  let pieces: Vec<&[u8]> = sep.split_inclusive("a||b||").collect();
+  assert_eq!(pieces, [&b"a||"[..], b"b||"]);
+
+  let pieces: Vec<&[u8]> = sep.split("").collect();
+  assert_eq!(pieces, [&b""[..]]);
+  assert_eq!(sep.split_inclusive("").count(), 0);
+
+  // separators overlapping an earlier one are skipped
+  let pieces: Vec<&[u8]> = search::Horspool::new("aa").overlapping(true).split("baaab").collect();
+  assert_eq!(pieces, [&b"b"[..], b"ab"]);
+}
+
+#[test]
+fn test_tarhio_ukkonen_search() {
+  let data = "GATTACAGATTCCAGATCACA";
+
+  assert_eq!(search::tarhio_ukkonen_search(data, "GATTACA", 0, 5, 0, data.len()), [(0, 0)]);
+  assert_eq!(search::tarhio_ukkonen_search(data, "GATTACA", 1, 5, 0, data.len()),
+             [(0, 0), (7, 1), (14, 1)]);
+  assert_eq!(search::tarhio_ukkonen_search(data, "GATTACA", 1, 5, 1, 20), [(7, 1)]);
+
+  let ocr = search::TarhioUkkonen::new("search", 2);
+  assert_eq!(ocr.find("a searoh rneans"), Some((2, 1)));
+  assert_eq!(ocr.find_all(b"5earcb"), [(0, 2)]);
+  assert_eq!(ocr.find_n("xxxxxx", 3), []);
+
+  // every window matches when all bytes may differ
+  assert_eq!(search::TarhioUkkonen::new("ab", 2).find_all("xyz"), [(0, 2), (1, 2)]);
+}
+
+#[test]
+fn test_wildcard_search() {
+  let signature = search::WildcardPattern::parse_hex("DE AD ?? EF").unwrap();
+  assert_eq!(signature.symbols(), [Some(0xde), Some(0xad), None, Some(0xef)]);
+  assert!(search::WildcardPattern::parse_hex("DE AD ?? E").is_none());
+  assert!(search::WildcardPattern::parse_hex("DE XX").is_none());
+
+  let data = [0x00, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0x00, 0xef, 0xde, 0xad, 0xef];
+  let bm = search::WildcardBoyerMoore::new(&signature);
+  assert_eq!(bm.find_all(&data), [1, 5]);
+  assert_eq!(bm.find_between(&data, 2, data.len()), Some(5));
+  assert_eq!(bm.matches(&data[6..]).count(), 0);
+
+  let gappy = search::WildcardPattern::new(vec![Some(b'a'), None, Some(b'a')]);
+  let bm = search::WildcardBoyerMoore::new(&gappy);
+  assert_eq!(bm.find_all("abababa"), [0, 4]);
+  assert_eq!(bm.overlapping(true).find_all("abababa"), [0, 2, 4]);
+}
+
+#[test]
+fn test_class_search() {
+  let phone = search::ClassPattern::parse("[0-9][0-9][0-9]-[0-9][0-9][0-9][0-9]").unwrap();
+  assert_eq!(phone.len(), 8);
+  let bmh = search::ClassHorspool::new(&phone);
+  assert_eq!(bmh.find_all("Call 555-0123 or 555-9876"), [5, 17]);
+  assert_eq!(bmh.find("Call 555-O123"), None);
+
+  let apple = search::ClassPattern::parse("[aA]pple").unwrap();
+  assert_eq!(search::ClassHorspool::new(&apple).find_all("Apple and apple"), [0, 10]);
+
+  let escaped = search::ClassPattern::parse(r"\[[x\-]\]").unwrap();
+  assert_eq!(search::ClassHorspool::new(&escaped).find_all("[y] [-] [x]"), [4, 8]);
+
+  // a dash first or last in a class is literal
+  let signs = search::ClassPattern::parse("[a-][-b]").unwrap();
+  assert_eq!(search::ClassHorspool::new(&signs).find_all("a- -b ab ba"), [0, 3, 6]);
+
+  assert!(search::ClassPattern::parse("[a-").is_none());
+  assert!(search::ClassPattern::parse("a]").is_none());
+  assert!(search::ClassPattern::parse("[z-a]").is_none());
+
+  // no multibyte characters within brackets, but outside them is fine
+  assert!(search::ClassPattern::parse("[é]").is_none());
+  assert!(search::ClassPattern::parse("[a-é]").is_none());
+  let cafe = search::ClassPattern::parse("[cC]afé").unwrap();
+  assert_eq!(search::ClassHorspool::new(&cafe).find_all("Café cafe café"), [0, 11]);
+
+  let mut vowels = search::ByteSet::new();
+  for &byte in b"aeiou" { vowels.insert(byte); }
+  let pattern = search::ClassPattern::new(vec![search::ByteSet::byte(b'b'), vowels, search::ByteSet::any()]);
+  let bmh = search::ClassHorspool::new(&pattern);
+  assert_eq!(bmh.find_all("bat bet bxt bob"), [0, 4, 12]);
+  assert_eq!(bmh.overlapping(true).find_all("bababa"), [0, 2]);
+}
+
+#[test]
+fn test_horspool_simd() {
+  // matches on either side of the 16 and 32 byte blocks, and in the tail
+  let mut data = "abcdefghijklmnopqrstuvwxyz".repeat(5);
+  data.push_str("needle");
+  let needle = "xyzab";
+
+  let positions = [23, 49, 75, 101];
+  assert_eq!(search::Horspool::new(needle).find_all(&data), positions);
+  assert_eq!(search::Horspool::new(needle).simd(false).find_all(&data), positions);
+  assert_eq!(search::boyer_moore_horspool_search(&data, needle, 2, 30, data.len()), [49, 75]);
+  assert_eq!(search::Horspool::new("needle").find(&data), Some(130));
+  assert_eq!(search::Horspool::new("a").find_all(&data), [0, 26, 52, 78, 104]);
+}
+
+#[test]
+fn test_rare_byte_search() {
+  let data = "the theme of the thesis: the zeal of the three thieves";
+
+  // 'z' is rarer than any other byte of the needle
+  let frequencies = search::ByteFrequencies::default();
+  assert!(frequencies.frequency(b'z') < frequencies.frequency(b'e'));
+  assert_eq!(frequencies.frequency(0xff), 0);
+  assert_eq!(search::RareByte::new("zeal").find(data), Some(29));
+
+  let the = search::RareByte::new("the");
+  assert_eq!(the.find_all(data), [0, 4, 13, 17, 25, 37]);
+  assert_eq!(the.find_between(data, 1, 10), Some(4));
+
+  // frequencies counted from a corpus
+  let corpus = search::ByteFrequencies::from_corpus(b"aaaabbbc");
+  assert_eq!(corpus.frequency(b'a'), 4);
+  assert_eq!(corpus.frequency(b'c'), 1);
+  let rare = search::RareByte::with_frequencies("abca", &corpus);
+  assert_eq!(rare.find_all("aabcaabcabca"), [1, 5]);
+  assert_eq!(rare.overlapping(true).find_all("aabcaabcabca"), [1, 5, 8]);
+}
+
+#[test]
+fn test_raita_sunday_search() {
+  let data = "abcabc";
+  for search_fn in [search::raita_search, search::sunday_search] {
+    assert_eq!(search_fn(data, "ab", 2, 0, 6), [0, 3]);
+    assert_eq!(search_fn(data, "ab", 1, 0, 6), [0]);
+    assert_eq!(search_fn(data, "bc", 2, 2, 6), [4]);
+    assert_eq!(search_fn(data, "", 2, 2, 6), [2]);
+    assert!(search_fn(data, "ax", 1, 0, 6).is_empty());
+    assert!(search_fn(data, "abc", 1, 0, 2).is_empty());
+
+    let data = "ประเทศไทย中华Việt Nam";
+    assert_eq!(search_fn(data, "ย中华", 1, 0, data.len()), [24]);
+    assert_eq!(search_fn(data, "Nam", 1, 0, data.len()), [40]);
+  }
+
+  for search_fn in [search::raita_search_overlapping, search::sunday_search_overlapping] {
+    assert_eq!(search_fn("aaaa", "aa", 5, 0, 4), [0, 1, 2]);
+    assert_eq!(search_fn("xabababax", "aba", 5, 0, 9), [1, 3, 5]);
+  }
+
+  assert_eq!(search::raita_search_bytes(b"\x00\xff\x00\xff", b"\xff\x00", 5, 0, 4), [1]);
+  assert_eq!(search::sunday_search_bytes(b"\x00\xff\x00\xff", b"\xff", 5, 0, 4), [1, 3]);
+
+  assert_eq!(search::Algorithm::Raita.search(data, "ca", 5, 0, 6), [2]);
+  assert_eq!(search::Algorithm::Sunday.search(data, "ca", 5, 0, 6), [2]);
+}
+
+#[test]
+fn test_raita_sunday_searchers() {
+  let raita = search::Raita::new("abc");
+  assert_eq!(raita.find("xxxabcabc"), Some(3));
+  assert_eq!(raita.find("banana"),    None);
+  assert_eq!(raita.find_between("xxxabcabc", 4, 9), Some(6));
+  assert_eq!(raita.find_n("xxxabcxabc", 1), [3]);
+  assert_eq!(raita.find_all("xxxabcxabc"),  [3, 7]);
+  assert_eq!(raita.matches("abcxxabc").count(), 2);
+
+  let sunday = search::Sunday::new("abc");
+  assert_eq!(sunday.find("xxxabcabc"), Some(3));
+  assert_eq!(sunday.find("banana"),    None);
+  assert_eq!(sunday.find_between("xxxabcabc", 4, 9), Some(6));
+  assert_eq!(sunday.find_n("xxxabcxabc", 1), [3]);
+  assert_eq!(sunday.find_all("xxxabcxabc"),  [3, 7]);
+  assert_eq!(sunday.matches("abcxxabc").count(), 2);
+
+  let sunday = search::Sunday::new("agag").overlapping(true);
+  assert_eq!(sunday.find_all("gagagagagcagagag"), [1, 3, 5, 10, 12]);
+}
+
+#[test]
+fn test_two_way_search() {
+  let data = "abcabc";
+  assert_eq!(search::two_way_search(data, "ab", 2, 0, 6), [0, 3]);
+  assert_eq!(search::two_way_search(data, "ca", 2, 0, 6), [2]);
+  assert!(search::two_way_search(data, "abc", 1, 0, 2).is_empty());
+
+  let data = "ประเทศไทย中华Việt Nam";
+  assert_eq!(search::two_way_search(data, "ย中华", 1, 0, data.len()), [24]);
+
+  // a periodic needle, where the searcher remembers what matched
+  let data = "abaabaabaabaabaabxabaabaabaab";
+  assert_eq!(search::two_way_search(data, "abaabaab", 5, 0, data.len()), [0, 9, 18]);
+  assert_eq!(search::two_way_search_overlapping(data, "abaabaab", 9, 0, data.len()),
+             [0, 3, 6, 9, 18, 21]);
+
+  assert_eq!(search::two_way_search_bytes(b"\x00\xff\x00\xff", b"\xff\x00", 5, 0, 4), [1]);
+
+  let two_way = search::TwoWay::new("abc");
+  assert_eq!(two_way.find("xxxabcabc"), Some(3));
+  assert_eq!(two_way.find("banana"),    None);
+  assert_eq!(two_way.find_between("xxxabcabc", 4, 9), Some(6));
+  assert_eq!(two_way.find_all("xxxabcxabc"),  [3, 7]);
+
+  let thresholds = search::Thresholds { two_way: true, ..search::Thresholds::default() };
+  assert_eq!(thresholds.choose(100000, b"needle"), search::Algorithm::TwoWay);
+  let data = "xyzzy ".repeat(100) + "plugh";
+  assert_eq!(search::findn_str_between_with(&thresholds, &data, "plugh", 1, 0, data.len()),
+             [600]);
+}
+
+#[test]
+fn test_knuth_morris_pratt_z_algorithm_search() {
+  for search_fn in [search::knuth_morris_pratt_search, search::z_algorithm_search] {
+    let data = "abcabc";
+    assert_eq!(search_fn(data, "ab", 2, 0, 6), [0, 3]);
+    assert_eq!(search_fn(data, "ca", 2, 0, 6), [2]);
+    assert_eq!(search_fn(data, "", 2, 1, 6), [1]);
+    assert!(search_fn(data, "abc", 1, 0, 2).is_empty());
+
+    let data = "ประเทศไทย中华Việt Nam";
+    assert_eq!(search_fn(data, "ย中华", 1, 0, data.len()), [24]);
+    assert_eq!(search_fn(data, "Nam", 1, 0, data.len()), [40]);
+  }
+
+  for search_fn in [search::knuth_morris_pratt_search_overlapping,
+                    search::z_algorithm_search_overlapping] {
+    assert_eq!(search_fn("aaaa", "aa", 5, 0, 4), [0, 1, 2]);
+    assert_eq!(search_fn("xabababax", "aba", 5, 0, 9), [1, 3, 5]);
+  }
+
+  assert_eq!(search::knuth_morris_pratt_search_bytes(b"\x00\xff\x00\xff", b"\xff\x00", 5, 0, 4),
+             [1]);
+  assert_eq!(search::z_algorithm_search_bytes(b"\x00\xff\x00\xff", b"\xff", 5, 0, 4), [1, 3]);
+
+  let kmp = search::KnuthMorrisPratt::new("abc");
+  assert_eq!(kmp.find_between("xxxabcabc", 4, 9), Some(6));
+  assert_eq!(kmp.find_all("xxxabcxabc"), [3, 7]);
+
+  // fed in chunks, keeping only how much of the needle has matched
+  let mut stream = kmp.stream();
+  assert_eq!(stream.feed(b"xxxa"), []);
+  assert_eq!(stream.feed(b"bcxab"), [3]);
+  assert_eq!(stream.feed(b"c"), [7]);
+
+  let kmp = search::KnuthMorrisPratt::new("aa").overlapping(true);
+  let mut stream = kmp.stream();
+  let found: Vec<usize> = b"xaaaa".iter().flat_map(|b| stream.feed(&[*b])).collect();
+  assert_eq!(found, [1, 2, 3]);
+  assert_eq!(kmp.stream().read_from(&b"aaa"[..]).unwrap(), [0, 1]);
+  assert_eq!(search::KnuthMorrisPratt::new("").stream().read_from(&b""[..]).unwrap(), [0]);
+
+  let z = search::ZAlgorithm::new("abc");
+  assert_eq!(z.find("banana"), None);
+  assert_eq!(z.find_n("xxxabcxabc", 1), [3]);
+}
+
+#[test]
+fn test_linear_tables() {
+  assert!(search::knuth_morris_pratt_borders(b"").is_empty());
+  assert_eq!(search::knuth_morris_pratt_borders(b"ANPANMAN"), [0, 0, 0, 1, 2, 0, 1, 2]);
+  assert_eq!(search::knuth_morris_pratt_borders(b"aabaaab"), [0, 1, 0, 1, 2, 2, 3]);
+
+  assert!(search::z_algorithm_prefixes(b"").is_empty());
+  assert_eq!(search::z_algorithm_prefixes(b"ANPANMAN"), [8, 0, 0, 2, 0, 0, 2, 0]);
+  assert_eq!(search::z_algorithm_prefixes(b"aabaaab"), [7, 1, 0, 2, 3, 1, 0]);
+
+  // the largest suffixes are the Z array of the reversed needle
+  for needle in ["heyyheyyheyy", "gcagagag", "ANPANMAN", "abaabaabaab", "ประเ"] {
+    let needle = needle.as_bytes();
+    let reversed: Vec<u8> = needle.iter().rev().copied().collect();
+    let mut prefixes = search::z_algorithm_prefixes(&reversed);
+    prefixes.reverse();
+    assert_eq!(search::boyer_moore_largest_suffixes(needle), prefixes);
+  }
+}
+
+#[test]
+fn test_matching_suffixes_against_linear_search() {
+  // repetitive needles lean on the good-suffix table,
+  // which KMP and Z check independently of Boyer-Moore
+  let data = "abaabaabaababaabaabbaabaabaabaabaaba".repeat(3);
+  for needle in ["abaab", "aabaabaab", "baabaabaa", "abab", "abaabaabaababa", "bb"] {
+    let bm = search::boyer_moore_search_overlapping(&data, needle, data.len(), 0, data.len());
+    assert_eq!(bm, search::knuth_morris_pratt_search_overlapping(&data, needle, data.len(),
+                                                                 0, data.len()));
+    assert_eq!(bm, search::z_algorithm_search_overlapping(&data, needle, data.len(),
+                                                          0, data.len()));
+
+    // and the linear Boyer-Moore variants, which carry their state across matches
+    assert_eq!(bm, search::TurboBoyerMoore::new(needle).overlapping(true).find_all(&data));
+    assert_eq!(bm, search::ApostolicoGiancarlo::new(needle).overlapping(true).find_all(&data));
+  }
+}
+
+#[test]
+fn test_zero_matches() {
+  // asking for no matches finds none, from every searcher,
+  // whatever the algorithm the adaptive search picks
+  let data = "abcabc";
+  let long = "xyzzy ".repeat(1000) + "abc";
+  for needle in ["abc", "a", ""] {
+    assert!(findn_str(data, needle, 0).is_empty());
+    assert!(findn_str(&long, needle, 0).is_empty());
+
+    for search_fn in [search::simple_search, search::simple_search_overlapping,
+                      search::boyer_moore_search, search::boyer_moore_horspool_search,
+                      search::boyer_moore_reverse_search,
+                      search::boyer_moore_horspool_reverse_search,
+                      search::boyer_moore_search_ignore_ascii_case,
+                      search::boyer_moore_search_ignore_case,
+                      search::turbo_boyer_moore_search, search::apostolico_giancarlo_search,
+                      search::raita_search, search::sunday_search, search::two_way_search,
+                      search::knuth_morris_pratt_search, search::z_algorithm_search] {
+      assert!(search_fn(data, needle, 0, 0, 6).is_empty());
+    }
+
+    assert!(search::RareByte::new(needle).find_n(data, 0).is_empty());
+    assert!(search::tarhio_ukkonen_search(data, needle, 1, 0, 0, 6).is_empty());
+    assert!(search::generic_boyer_moore_search(data.as_bytes(), needle.as_bytes(), 0, 0, 6)
+              .is_empty());
+    assert!(search::StrSearcher::new(needle).find_n(data, 0).is_empty());
+  }
+
+  assert!(search::single_byte_search(data, "a", 0, 0, 6).is_empty());
+  assert!(search::wu_manber_search(data, &["abc", "bc"], 0, 0, 6).is_empty());
+  assert_eq!(search::replacen("abcabc", "b", "x", 0), "abcabc");
+}